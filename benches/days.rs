@@ -0,0 +1,49 @@
+//! Criterion benchmarks of every day on its puzzle input, `data/day<N>/input` or the directory
+//! pointed to by `AOC_INPUTS`. Days without an input are skipped.
+
+use aoc24::{
+    input::{
+        Inputs,
+        PUZZLE_INPUT,
+    },
+    registry,
+    solution::Part,
+};
+use criterion::{
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use std::hint::black_box;
+
+fn days(c: &mut Criterion) {
+    let dir = std::env::var("AOC_INPUTS")
+        .unwrap_or_else(|_| concat!(env!("CARGO_MANIFEST_DIR"), "/data").to_string());
+    let inputs = Inputs::new(dir);
+
+    for day in registry::DAYS.iter() {
+        let Ok(data) = inputs.load(day.number, PUZZLE_INPUT) else {
+            eprintln!("Skipping day {}: no puzzle input", day.number);
+            continue;
+        };
+        let Ok(parsed) = (day.parse)(&data) else {
+            eprintln!(
+                "Skipping day {}: the puzzle input does not parse",
+                day.number
+            );
+            continue;
+        };
+
+        let mut group = c.benchmark_group(format!("day{}", day.number));
+        group.bench_function("parse", |b| b.iter(|| (day.parse)(black_box(&data))));
+        for part in Part::ALL {
+            group.bench_function(format!("part{}", part), |b| {
+                b.iter(|| parsed.solve(black_box(part)))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+//! ANSI escape sequences for colored and animated terminal output.
+
+use std::{
+    fmt::Display,
+    io::{
+        self,
+        Write,
+    },
+    thread,
+    time::Duration,
+};
+
+pub const RESET: &str = "\x1b[0m";
+pub const BOLD: &str = "\x1b[1m";
+pub const DIM: &str = "\x1b[2m";
+pub const RED: &str = "\x1b[31m";
+pub const GREEN: &str = "\x1b[32m";
+pub const YELLOW: &str = "\x1b[33m";
+pub const BLUE: &str = "\x1b[34m";
+pub const MAGENTA: &str = "\x1b[35m";
+pub const CYAN: &str = "\x1b[36m";
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CLEAR_LINE: &str = "\x1b[K";
+const CURSOR_HOME: &str = "\x1b[H";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+/// `value` drawn with the given styles, e.g. `paint('#', &[BOLD, RED])`.
+pub fn paint(
+    value: impl Display,
+    styles: &[&str],
+) -> String {
+    format!("{}{}{}", styles.concat(), value, RESET)
+}
+
+/// Draws each frame over the previous one, at `fps` frames per second, or as fast as possible
+/// when `fps` is not positive. Fails before drawing anything when `fps` is too small for a delay.
+pub fn animate(
+    out: &mut impl Write,
+    frames: impl IntoIterator<Item = String>,
+    fps: f64,
+) -> io::Result<()> {
+    let delay = (fps > 0.0)
+        .then(|| {
+            Duration::try_from_secs_f64(1.0 / fps).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("too few frames per second to wait between frames: {}", err),
+                )
+            })
+        })
+        .transpose()?;
+
+    write!(out, "{}{}", HIDE_CURSOR, CLEAR_SCREEN)?;
+    let result = frames.into_iter().try_for_each(|frame| {
+        write!(out, "{}", CURSOR_HOME)?;
+        for line in frame.lines() {
+            writeln!(out, "{}{}", line, CLEAR_LINE)?;
+        }
+        out.flush()?;
+
+        if let Some(delay) = delay {
+            thread::sleep(delay);
+        }
+        Ok(())
+    });
+    write!(out, "{}", SHOW_CURSOR)?;
+    out.flush()?;
+
+    result
+}
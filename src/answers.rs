@@ -0,0 +1,145 @@
+use crate::{
+    answer::Answer,
+    solution::Part,
+};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+/// Expected answers, keyed by day, part and file name:
+///
+/// ```toml
+/// [day12.part2]
+/// ex1 = 1206
+/// ex2 = 368
+/// input = 123456
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part, String), Answer>,
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, source: io::Error },
+    Toml(toml::de::Error),
+    Invalid(String),
+}
+
+impl Display for AnswersError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(
+                    f,
+                    "could not read answers file '{}': {}",
+                    path.display(),
+                    source
+                )
+            }
+            AnswersError::Toml(source) => write!(f, "invalid answers file: {}", source),
+            AnswersError::Invalid(reason) => write!(f, "invalid answers file: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnswersError::Io { source, .. } => Some(source),
+            AnswersError::Toml(source) => Some(source),
+            AnswersError::Invalid(_) => None,
+        }
+    }
+}
+
+fn day(key: &str) -> Result<u8, AnswersError> {
+    key.strip_prefix("day")
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| AnswersError::Invalid(format!("expected 'day<N>', found '{}'", key)))
+}
+
+fn part(key: &str) -> Result<Part, AnswersError> {
+    key.strip_prefix("part")
+        .and_then(|number| number.parse().ok())
+        .ok_or_else(|| AnswersError::Invalid(format!("expected 'part<1|2>', found '{}'", key)))
+}
+
+fn table<'a>(
+    value: &'a toml::Value,
+    key: &str,
+) -> Result<&'a toml::Table, AnswersError> {
+    value
+        .as_table()
+        .ok_or_else(|| AnswersError::Invalid(format!("'{}' should be a table", key)))
+}
+
+impl Answers {
+    pub fn parse(data: &str) -> Result<Self, AnswersError> {
+        let root = data.parse::<toml::Table>().map_err(AnswersError::Toml)?;
+        let mut answers = BTreeMap::new();
+
+        for (day_key, parts) in root.iter() {
+            let day = day(day_key)?;
+
+            for (part_key, files) in table(parts, day_key)?.iter() {
+                let part = part(part_key)?;
+
+                for (file, value) in table(files, part_key)?.iter() {
+                    let answer = match value {
+                        toml::Value::Integer(value) => Answer::from(*value),
+                        toml::Value::String(value) => Answer::from(value.as_str()),
+                        _ => {
+                            return Err(AnswersError::Invalid(format!(
+                                "'{}.{}.{}' should be an integer or a string",
+                                day_key, part_key, file
+                            )))
+                        }
+                    };
+                    answers.insert((day, part, file.clone()), answer);
+                }
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let data = std::fs::read_to_string(path).map_err(|source| {
+            AnswersError::Io {
+                path: path.to_path_buf(),
+                source,
+            }
+        })?;
+        Self::parse(&data)
+    }
+
+    pub fn expected(
+        &self,
+        day: u8,
+        part: Part,
+        file: &str,
+    ) -> Option<&Answer> {
+        self.answers.get(&(day, part, file.to_string()))
+    }
+
+    /// Every recorded answer as `(day, part, file, answer)`, ordered by day then part.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, Part, &str, &Answer)> {
+        self.answers
+            .iter()
+            .map(|((day, part, file), answer)| (*day, *part, file.as_str(), answer))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    error::{
+        ParseError,
+        SolveError,
+    },
+    registry::Day,
+    solution::Part,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::{
+    fmt::{
+        Display,
+        Write,
+    },
+    io,
+    path::Path,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// Mean duration of each step of a day, in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub parse_ns: u64,
+    pub part1_ns: u64,
+    pub part2_ns: u64,
+}
+
+impl Timing {
+    fn steps(&self) -> [(&'static str, u64); 3] {
+        [
+            ("parse", self.parse_ns),
+            ("part 1", self.part1_ns),
+            ("part 2", self.part2_ns),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub enum BenchError {
+    Parse(ParseError),
+    Solve(Part, SolveError),
+}
+
+impl Display for BenchError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            BenchError::Parse(source) => write!(f, "could not parse input at {}", source),
+            BenchError::Solve(part, source) => write!(f, "part {}: {}", part, source),
+        }
+    }
+}
+
+impl std::error::Error for BenchError {
+}
+
+fn mean<T, E>(
+    iterations: u32,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<u64, E> {
+    let mut total = Duration::ZERO;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let result = f()?;
+        total += start.elapsed();
+        std::hint::black_box(result);
+    }
+
+    Ok((total.as_nanos() / iterations.max(1) as u128) as u64)
+}
+
+/// Times parsing and both parts of `day` on `data`, averaged over `iterations` runs.
+pub fn bench_day(
+    day: &Day,
+    data: &str,
+    iterations: u32,
+) -> Result<Timing, BenchError> {
+    let parse_ns = mean(iterations, || (day.parse)(data)).map_err(BenchError::Parse)?;
+
+    let parsed = (day.parse)(data).map_err(BenchError::Parse)?;
+    let part1_ns = mean(iterations, || parsed.solve(Part::First))
+        .map_err(|err| BenchError::Solve(Part::First, err))?;
+    let part2_ns = mean(iterations, || parsed.solve(Part::Second))
+        .map_err(|err| BenchError::Solve(Part::Second, err))?;
+
+    Ok(Timing {
+        day: day.number,
+        parse_ns,
+        part1_ns,
+        part2_ns,
+    })
+}
+
+fn format_duration(ns: u64) -> String {
+    let ns = ns as f64;
+    if ns < 1e3 {
+        format!("{:.0}ns", ns)
+    } else if ns < 1e6 {
+        format!("{:.1}µs", ns / 1e3)
+    } else if ns < 1e9 {
+        format!("{:.2}ms", ns / 1e6)
+    } else {
+        format!("{:.2}s", ns / 1e9)
+    }
+}
+
+fn change(
+    ns: u64,
+    baseline_ns: u64,
+) -> f64 {
+    if baseline_ns == 0 {
+        0.0
+    } else {
+        (ns as f64 - baseline_ns as f64) / baseline_ns as f64 * 100.0
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub timings: Vec<Timing>,
+}
+
+impl Report {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let data = std::fs::read_to_string(path)?;
+        serde_json::from_str(&data).map_err(io::Error::from)
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> io::Result<()> {
+        let data = serde_json::to_string_pretty(self).map_err(io::Error::from)?;
+        std::fs::write(path, data)
+    }
+
+    fn baseline_for(
+        baseline: Option<&Report>,
+        day: u8,
+    ) -> Option<&Timing> {
+        baseline.and_then(|baseline| baseline.timings.iter().find(|timing| timing.day == day))
+    }
+
+    /// One row per day, with the change against `baseline` when it has timings for that day.
+    pub fn table(
+        &self,
+        baseline: Option<&Report>,
+    ) -> String {
+        let mut table = String::new();
+        let _ = writeln!(
+            table,
+            "{:>4} | {:>18} | {:>18} | {:>18}",
+            "day", "parse", "part 1", "part 2"
+        );
+        let _ = writeln!(table, "{}", "-".repeat(67));
+
+        for timing in self.timings.iter() {
+            let previous = Self::baseline_for(baseline, timing.day);
+            let _ = write!(table, "{:>4}", timing.day);
+
+            for (idx, (_, ns)) in timing.steps().iter().enumerate() {
+                let cell = match previous {
+                    Some(previous) => {
+                        let baseline_ns = previous.steps()[idx].1;
+                        format!(
+                            "{} ({:+.0}%)",
+                            format_duration(*ns),
+                            change(*ns, baseline_ns)
+                        )
+                    }
+                    None => format_duration(*ns),
+                };
+                let _ = write!(table, " | {:>18}", cell);
+            }
+
+            let _ = writeln!(table);
+        }
+
+        table
+    }
+
+    /// Steps slower than in `baseline` by more than `threshold` percent.
+    pub fn regressions(
+        &self,
+        baseline: &Report,
+        threshold: f64,
+    ) -> Vec<String> {
+        let mut regressions = Vec::new();
+
+        for timing in self.timings.iter() {
+            if let Some(previous) = Self::baseline_for(Some(baseline), timing.day) {
+                for ((step, ns), (_, baseline_ns)) in timing.steps().iter().zip(previous.steps()) {
+                    let change = change(*ns, baseline_ns);
+                    if change > threshold {
+                        regressions.push(format!(
+                            "day {} {}: {} -> {} ({:+.0}%)",
+                            timing.day,
+                            step,
+                            format_duration(baseline_ns),
+                            format_duration(*ns),
+                            change
+                        ));
+                    }
+                }
+            }
+        }
+
+        regressions
+    }
+}
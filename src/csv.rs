@@ -0,0 +1,15 @@
+use std::fmt::Write;
+
+/// CSV text with a `header` line, then one line per row. Fields are written as they are, so they
+/// should not hold commas or line endings.
+pub fn table<const N: usize>(
+    header: [&str; N],
+    rows: impl IntoIterator<Item = [String; N]>,
+) -> String {
+    let mut csv = String::new();
+    let _ = writeln!(csv, "{}", header.join(","));
+    for row in rows {
+        let _ = writeln!(csv, "{}", row.join(","));
+    }
+    csv
+}
@@ -1,3 +1,12 @@
+use crate::{
+    answer::Answer,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    solution::Solution,
+};
 use nom::{
     self,
     character::complete::{
@@ -14,7 +23,6 @@ use nom::{
         separated_pair,
         terminated,
     },
-    Finish,
 };
 use std::collections::HashMap;
 
@@ -32,24 +40,6 @@ fn parse(input: &str) -> nom::IResult<&str, (Vec<i32>, Vec<i32>)> {
     ))(input)
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let (_, (mut left, mut right)) = parse(data).finish().unwrap();
-
-    left.sort();
-    right.sort();
-
-    let sum: i32 = left
-        .iter()
-        .zip(right.iter())
-        .map(|(l, r)| (l - r).abs())
-        .sum();
-
-    println!("{}: Total distance between lists is '{}'", name, sum);
-}
-
 fn occurrences(numbers: &[i32]) -> HashMap<i32, i32> {
     let mut occurrences = HashMap::new();
 
@@ -60,29 +50,44 @@ fn occurrences(numbers: &[i32]) -> HashMap<i32, i32> {
     occurrences
 }
 
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let (_, (left, right)) = parse(data).finish().unwrap();
+pub struct Day1;
 
-    let left_occurrences = occurrences(&left);
-    let right_occurrences = occurrences(&right);
+impl Solution for Day1 {
+    type Input = (Vec<i32>, Vec<i32>);
 
-    let sum: i32 = left_occurrences
-        .iter()
-        .map(|(number, count)| {
-            let right_count = right_occurrences.get(number).unwrap_or(&0);
-            number * count * right_count
-        })
-        .sum();
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
 
-    println!("{}: Similarity between lists is '{}'", name, sum);
-}
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let (mut left, mut right) = input.clone();
 
-pub fn run() {
-    first("First example", include_str!("data/day1/ex1"));
-    first("First", include_str!("data/day1/input"));
-    second("Second example", include_str!("data/day1/ex1"));
-    second("Second", include_str!("data/day1/input"));
+        left.sort();
+        right.sort();
+
+        let sum: i32 = left
+            .iter()
+            .zip(right.iter())
+            .map(|(l, r)| (l - r).abs())
+            .sum();
+
+        Ok(sum.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let (left, right) = input;
+
+        let left_occurrences = occurrences(left);
+        let right_occurrences = occurrences(right);
+
+        let sum: i32 = left_occurrences
+            .iter()
+            .map(|(number, count)| {
+                let right_count = right_occurrences.get(number).unwrap_or(&0);
+                number * count * right_count
+            })
+            .sum();
+
+        Ok(sum.into())
+    }
 }
@@ -1,161 +1,299 @@
-use itertools::Itertools;
-use std::collections::HashSet;
+use crate::{
+    answer::Answer,
+    error::{
+        ParseError,
+        SolveError,
+    },
+    grid::{
+        parse_chars,
+        Direction,
+        Grid,
+        Point,
+    },
+    solution::Solution,
+};
+use std::ops::RangeInclusive;
 
-#[derive(Clone, Debug)]
-struct Topology {
-    nodes: Vec<Vec<i32>>,
-    num_rows: i32,
-    num_cols: i32,
+/// What makes a trail: the heights it starts and ends at, the height differences allowed between
+/// two consecutive cells, and whether it can move diagonally.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrailRules {
+    start: i32,
+    end: i32,
+    steps: RangeInclusive<i32>,
+    diagonal: bool,
 }
 
-const TRANSLATIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+impl Default for TrailRules {
+    /// Trails of the puzzle, climbing one unit at a time from 0 to 9.
+    fn default() -> Self {
+        Self {
+            start: 0,
+            end: 9,
+            steps: 1..=1,
+            diagonal: false,
+        }
+    }
+}
 
-impl Topology {
-    fn new(nodes: Vec<Vec<i32>>) -> Self {
-        let num_rows = nodes.len() as i32;
-        let num_cols = nodes[0].len() as i32;
-
-        Topology {
-            nodes,
-            num_rows,
-            num_cols,
+impl TrailRules {
+    /// Fails when `steps` allows both climbing and descending, or staying level, as trails could
+    /// then go round in circles.
+    pub fn new(
+        start: i32,
+        end: i32,
+        steps: RangeInclusive<i32>,
+        diagonal: bool,
+    ) -> Result<Self, String> {
+        if steps.is_empty() || (*steps.start() <= 0 && *steps.end() >= 0) {
+            return Err(format!(
+                "steps should either all climb or all descend, got {} to {}",
+                steps.start(),
+                steps.end()
+            ));
         }
+
+        Ok(Self {
+            start,
+            end,
+            steps,
+            diagonal,
+        })
     }
 
-    fn trailheads(&self) -> Vec<(i32, i32)> {
-        let mut trailheads = Vec::new();
+    fn directions(&self) -> &'static [Direction] {
+        if self.diagonal {
+            &Direction::ALL
+        } else {
+            &Direction::ORTHOGONAL
+        }
+    }
+}
 
-        for (row_idx, row) in self.nodes.iter().enumerate() {
-            for (col_idx, height) in row.iter().enumerate() {
-                if *height == 0 {
-                    trailheads.push((row_idx as i32, col_idx as i32));
-                }
-            }
+/// Set of summits, as bits indexed by the order in which the summits were found.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Summits {
+    bits: Vec<u64>,
+}
+
+impl Summits {
+    fn single(index: usize) -> Self {
+        let mut bits = vec![0; index / 64 + 1];
+        bits[index / 64] = 1 << (index % 64);
+        Self { bits }
+    }
+
+    fn union(
+        &mut self,
+        other: &Summits,
+    ) {
+        if self.bits.len() < other.bits.len() {
+            self.bits.resize(other.bits.len(), 0);
         }
+        for (bits, other) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *bits |= other;
+        }
+    }
 
-        trailheads
+    fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+}
+
+/// Summits reachable from, and number of distinct trails starting at, every cell.
+#[derive(Clone, Debug)]
+pub struct Trails {
+    summits: Grid<Summits>,
+    ratings: Grid<u64>,
+}
+
+impl Trails {
+    /// Number of summits reachable from `point`.
+    pub fn score(
+        &self,
+        point: Point,
+    ) -> usize {
+        self.summits[point].len()
     }
 
-    fn neighbours(
+    /// Number of distinct trails from `point` to a summit.
+    pub fn rating(
         &self,
-        row_idx: i32,
-        col_idx: i32,
-    ) -> Vec<(i32, i32, i32)> {
-        TRANSLATIONS
+        point: Point,
+    ) -> u64 {
+        self.ratings[point]
+    }
+}
+
+/// Trails from a cell, enumerated depth first as they are needed.
+pub struct Paths<'a> {
+    topology: &'a Topology,
+    rules: &'a TrailRules,
+    stack: Vec<Vec<Point>>,
+}
+
+impl Iterator for Paths<'_> {
+    type Item = Vec<Point>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(path) = self.stack.pop() {
+            let last = *path.last()?;
+            if self.topology.nodes[last] == Some(self.rules.end) {
+                return Some(path);
+            }
+
+            for next in self.topology.steps(last, self.rules) {
+                let mut longer = path.clone();
+                longer.push(next);
+                self.stack.push(longer);
+            }
+        }
+
+        None
+    }
+}
+
+/// Heights of the map, `None` for impassable cells.
+#[derive(Clone, Debug)]
+pub struct Topology {
+    nodes: Grid<Option<i32>>,
+}
+
+impl Topology {
+    fn new(nodes: Grid<Option<i32>>) -> Self {
+        Topology { nodes }
+    }
+
+    pub fn trailheads<'a>(
+        &'a self,
+        rules: &'a TrailRules,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.nodes
             .iter()
-            .filter_map(|(row_delta, col_delta)| {
-                let row_idx = row_idx + row_delta;
-                let col_idx = col_idx + col_delta;
-
-                if row_idx < 0 || row_idx >= self.num_rows {
-                    return None;
-                }
-                if col_idx < 0 || col_idx >= self.num_cols {
-                    return None;
-                }
-
-                Some((
-                    row_idx,
-                    col_idx,
-                    self.nodes[row_idx as usize][col_idx as usize],
-                ))
+            .filter(|(_, height)| **height == Some(rules.start))
+            .map(|(point, _)| point)
+    }
+
+    /// Cells a trail can go to from `point`.
+    fn steps<'a>(
+        &'a self,
+        point: Point,
+        rules: &'a TrailRules,
+    ) -> impl Iterator<Item = Point> + 'a {
+        let height = self.nodes[point];
+        self.nodes
+            .neighbours_in(point, rules.directions())
+            .filter(move |neighbour| {
+                height
+                    .zip(self.nodes[*neighbour])
+                    .is_some_and(|(height, next)| rules.steps.contains(&(next - height)))
             })
-            .collect()
     }
 
-    fn find_paths(&self) -> Vec<Vec<(i32, i32, i32)>> {
-        let trailheads = self.trailheads();
+    /// Scores and ratings of every cell, computed from the trail ends back so that each cell only
+    /// combines the results of the cells it can step to. Fails when a rating does not fit in a
+    /// `u64`.
+    pub fn trails(
+        &self,
+        rules: &TrailRules,
+    ) -> Result<Trails, SolveError> {
+        let mut summits = self.nodes.map(|_| Summits::default());
+        let mut ratings = self.nodes.map(|_| 0u64);
 
-        let mut paths = Vec::new();
-        trailheads
-            .into_iter()
-            .for_each(|(row_idx, col_idx)| paths.push(vec![(row_idx, col_idx, 0)]));
+        let mut order: Vec<(Point, i32)> = self
+            .nodes
+            .iter()
+            .filter_map(|(point, height)| height.map(|height| (point, height)))
+            .collect();
+        let climbing = *rules.steps.start() > 0;
+        order.sort_unstable_by_key(|(_, height)| if climbing { -height } else { *height });
 
-        loop {
-            if paths
-                .iter()
-                .all(|path| path.last().is_some_and(|(_, _, height)| *height == 9))
-            {
-                break;
+        let mut num_summits = 0;
+        for (point, height) in order {
+            if height == rules.end {
+                summits[point] = Summits::single(num_summits);
+                ratings[point] = 1;
+                num_summits += 1;
+                continue;
             }
 
-            let mut new_paths = Vec::new();
-            for path in paths {
-                if let Some((row_idx, col_idx, height)) = path.last() {
-                    if *height == 9 {
-                        new_paths.push(path);
-                        continue;
-                    }
-
-                    for neighbour in self.neighbours(*row_idx, *col_idx).into_iter().filter(
-                        |(row_idx, col_idx, neighbour_height)| {
-                            !path.contains(&(*row_idx, *col_idx, *neighbour_height))
-                                && *neighbour_height == height + 1
-                        },
-                    ) {
-                        let (row_idx, col_idx, height) = neighbour;
-                        let mut new_path = path.clone();
-                        new_path.push((row_idx, col_idx, height));
-                        new_paths.push(new_path);
-                    }
-                }
+            let mut reachable = Summits::default();
+            for next in self.steps(point, rules) {
+                reachable.union(&summits[next]);
+                ratings[point] = ratings[point]
+                    .checked_add(ratings[next])
+                    .ok_or_else(|| SolveError::Overflow(format!("the rating of {}", point)))?;
             }
+            summits[point] = reachable;
+        }
+
+        Ok(Trails { summits, ratings })
+    }
 
-            paths = new_paths;
+    /// Every trail from `start` to the end height, for debugging.
+    pub fn paths<'a>(
+        &'a self,
+        start: Point,
+        rules: &'a TrailRules,
+    ) -> Paths<'a> {
+        Paths {
+            topology: self,
+            rules,
+            stack: vec![vec![start]],
         }
+    }
 
-        paths
+    /// Sums of the scores and of the ratings of every trailhead.
+    pub fn totals(
+        &self,
+        rules: &TrailRules,
+    ) -> Result<(usize, u64), SolveError> {
+        let trails = self.trails(rules)?;
+        self.trailheads(rules)
+            .try_fold((0, 0u64), |(score, rating), trailhead| {
+                let rating = rating
+                    .checked_add(trails.rating(trailhead))
+                    .ok_or_else(|| SolveError::Overflow("the total rating".to_string()))?;
+                Ok((score + trails.score(trailhead), rating))
+            })
     }
 }
 
-fn parse(data: &str) -> Topology {
-    Topology::new(
-        data.lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as i32)
-                    .collect()
-            })
-            .collect(),
+fn parse(data: &str) -> Result<Topology, ParseError> {
+    parse_chars(
+        data,
+        |c| {
+            match c {
+                '.' => Some(None),
+                c => c.to_digit(36).map(|height| Some(height as i32)),
+            }
+        },
+        "a height (0-9, then a-z up to 35) or '.'",
     )
+    .map(Topology::new)
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let topology = parse(data);
-    let paths = topology.find_paths();
-
-    let score = paths
-        .iter()
-        .into_group_map_by(|path| path.first().unwrap())
-        .values()
-        .map(|v| {
-            v.iter()
-                .filter_map(|path| path.last())
-                .collect::<HashSet<_>>()
-                .len()
-        })
-        .sum::<usize>();
+pub struct Day10;
 
-    println!("{}: Score is '{:?}'", name, score);
-}
+impl Solution for Day10 {
+    type Input = Topology;
 
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let topology = parse(data);
-    let paths = topology.find_paths();
-    let score = paths.len();
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
 
-    println!("{}: Score is '{:?}'", name, score);
-}
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let (score, _) = input.totals(&TrailRules::default())?;
 
-pub fn run() {
-    first("First example", include_str!("data/day10/ex1"));
-    first("First", include_str!("data/day10/input"));
-    second("Second example", include_str!("data/day10/ex1"));
-    second("Second", include_str!("data/day10/input"));
+        Ok(score.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let (_, rating) = input.totals(&TrailRules::default())?;
+
+        Ok(rating.into())
+    }
 }
@@ -1,97 +1,428 @@
+use crate::{
+    answer::Answer,
+    csv,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    solution::Solution,
+};
 use nom::{
+    branch::alt,
+    bytes::complete::tag,
     character::complete::{
+        char,
         line_ending,
+        multispace0,
+        space0,
         space1,
+        u64,
     },
     combinator::{
         all_consuming,
         map,
         opt,
+        value,
+    },
+    multi::{
+        many1,
+        separated_list1,
+    },
+    sequence::{
+        delimited,
+        preceded,
+        separated_pair,
+        terminated,
     },
-    multi::separated_list1,
-    sequence::terminated,
-    Finish,
     IResult,
 };
-use std::collections::HashMap;
+use num_bigint::BigUint;
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    fmt::Display,
+};
 
 #[derive(Clone, Debug)]
-struct Stone {
-    number: i64,
+pub struct Stone {
+    number: u64,
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Stone>> {
-    all_consuming(terminated(
+/// Number of stones, which can grow past any fixed size integer after enough blinks.
+pub trait Count: Clone + Display {
+    fn one() -> Self;
+
+    fn checked_add(
+        &self,
+        other: &Self,
+    ) -> Option<Self>;
+}
+
+impl Count for u64 {
+    fn one() -> Self {
+        1
+    }
+
+    fn checked_add(
+        &self,
+        other: &Self,
+    ) -> Option<Self> {
+        u64::checked_add(*self, *other)
+    }
+}
+
+impl Count for u128 {
+    fn one() -> Self {
+        1
+    }
+
+    fn checked_add(
+        &self,
+        other: &Self,
+    ) -> Option<Self> {
+        u128::checked_add(*self, *other)
+    }
+}
+
+impl Count for BigUint {
+    fn one() -> Self {
+        BigUint::from(1u8)
+    }
+
+    fn checked_add(
+        &self,
+        other: &Self,
+    ) -> Option<Self> {
+        Some(self + other)
+    }
+}
+
+/// Transformation of a stone when blinking.
+pub trait Rule: Send + Sync {
+    /// Stones replacing `stone`, or `None` when the rule does not apply to it.
+    fn apply(
+        &self,
+        stone: u64,
+    ) -> Option<Result<Vec<u64>, SolveError>>;
+}
+
+fn num_digits(number: u64) -> u32 {
+    number.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Condition {
+    /// The stone has this number.
+    Is(u64),
+    /// The number of the stone has an even number of digits.
+    EvenDigits,
+    Always,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// The stone is replaced by one with this number.
+    Become(u64),
+    /// The stone is replaced by two, with the left and right halves of its digits.
+    Split,
+    Multiply(u64),
+    Add(u64),
+}
+
+/// Rule of the rule language, `<condition> -> <action>`, where the condition is a number, `even`
+/// or `*`, and the action a number, `split`, `*<number>` or `+<number>`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SimpleRule {
+    pub condition: Condition,
+    pub action: Action,
+}
+
+impl Rule for SimpleRule {
+    fn apply(
+        &self,
+        stone: u64,
+    ) -> Option<Result<Vec<u64>, SolveError>> {
+        let applies = match self.condition {
+            Condition::Is(number) => stone == number,
+            Condition::EvenDigits => num_digits(stone).is_multiple_of(2),
+            Condition::Always => true,
+        };
+        if !applies {
+            return None;
+        }
+
+        let overflow = |op: char, operand: u64| {
+            SolveError::Overflow(format!("stone {} {} {}", stone, op, operand))
+        };
+        let stones = match self.action {
+            Action::Become(number) => Ok(vec![number]),
+            Action::Split => {
+                let half = 10u64.pow(num_digits(stone) / 2);
+                Ok(vec![stone / half, stone % half])
+            }
+            Action::Multiply(factor) => {
+                stone
+                    .checked_mul(factor)
+                    .map(|stone| vec![stone])
+                    .ok_or_else(|| overflow('*', factor))
+            }
+            Action::Add(term) => {
+                stone
+                    .checked_add(term)
+                    .map(|stone| vec![stone])
+                    .ok_or_else(|| overflow('+', term))
+            }
+        };
+
+        Some(stones)
+    }
+}
+
+fn condition(input: &str) -> IResult<&str, Condition> {
+    alt((
+        map(u64, Condition::Is),
+        value(Condition::EvenDigits, tag("even")),
+        value(Condition::Always, char('*')),
+    ))(input)
+}
+
+fn action(input: &str) -> IResult<&str, Action> {
+    alt((
+        map(u64, Action::Become),
+        value(Action::Split, tag("split")),
+        map(preceded(char('*'), u64), Action::Multiply),
+        map(preceded(char('+'), u64), Action::Add),
+    ))(input)
+}
+
+fn rules(input: &str) -> IResult<&str, Vec<SimpleRule>> {
+    let separator = many1(delimited(space0, alt((tag(";"), line_ending)), space0));
+
+    all_consuming(delimited(
+        multispace0,
         separated_list1(
-            space1,
-            map(nom::character::complete::i64, |number| Stone { number }),
+            separator,
+            map(
+                separated_pair(condition, delimited(space0, tag("->"), space0), action),
+                |(condition, action)| SimpleRule { condition, action },
+            ),
         ),
-        opt(line_ending),
+        multispace0,
     ))(input)
 }
 
-fn solve(
-    name: &str,
-    data: &str,
-    iterations: usize,
-) {
-    let (_, init_stones) = parse(data).finish().unwrap();
-    let mut stones = HashMap::new();
-
-    for stone in init_stones.into_iter() {
-        stones
-            .entry(stone.number)
-            .and_modify(|count| *count += 1)
-            .or_insert(1);
-    }
-
-    for _ in 0..iterations {
-        let mut new_stones = HashMap::new();
-        for (number, count) in stones.into_iter() {
-            if number == 0 {
-                new_stones
-                    .entry(1)
-                    .and_modify(|new_count| *new_count += count)
-                    .or_insert(count);
-            } else {
-                let number_str = number.to_string();
-
-                if number_str.len() % 2 == 0 {
-                    let half = number_str.len() / 2;
-                    let (left, right) = number_str.split_at(half);
-                    let left = left.parse::<i64>().unwrap_or_default();
-                    let right = right.parse::<i64>().unwrap_or_default();
-
-                    new_stones
-                        .entry(left)
-                        .and_modify(|new_count| *new_count += count)
-                        .or_insert(count);
-                    new_stones
-                        .entry(right)
-                        .and_modify(|new_count| *new_count += count)
-                        .or_insert(count);
-                } else {
-                    new_stones
-                        .entry(number * 2024)
-                        .and_modify(|new_count| *new_count += count)
-                        .or_insert(count);
-                }
-            }
+/// Rules tried in order on each stone, the first one that applies replaces it. Stones no rule
+/// applies to stay as they are.
+pub struct Rules {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Rules {
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self { rules }
+    }
+
+    /// Rules written in the rule language of `SimpleRule`, separated by `;` or line endings.
+    pub fn parse(data: &str) -> Result<Self, ParseError> {
+        let rules = finish(data, rules(data))?;
+        Ok(Self::new(
+            rules
+                .into_iter()
+                .map(|rule| Box::new(rule) as Box<dyn Rule>)
+                .collect(),
+        ))
+    }
+
+    /// Rules of the puzzle.
+    pub fn standard() -> Self {
+        Self::parse("0 -> 1; even -> split; * -> *2024").expect("the standard rules are valid")
+    }
+
+    fn apply(
+        &self,
+        stone: u64,
+    ) -> Result<Vec<u64>, SolveError> {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.apply(stone))
+            .unwrap_or(Ok(vec![stone]))
+    }
+}
+
+/// Number of stones with each number.
+pub type Stones<C> = HashMap<u64, C>;
+
+fn add<C: Count>(
+    stones: &mut Stones<C>,
+    number: u64,
+    count: &C,
+) -> Result<(), SolveError> {
+    match stones.get_mut(&number) {
+        Some(total) => {
+            *total = total
+                .checked_add(count)
+                .ok_or_else(|| SolveError::Overflow(format!("the count of stone {}", number)))?;
+        }
+        None => {
+            stones.insert(number, count.clone());
+        }
+    }
+    Ok(())
+}
+
+pub fn initial<C: Count>(stones: &[Stone]) -> Result<Stones<C>, SolveError> {
+    let mut counts = HashMap::new();
+    for stone in stones.iter() {
+        add(&mut counts, stone.number, &C::one())?;
+    }
+    Ok(counts)
+}
+
+pub fn blink<C: Count>(
+    stones: &Stones<C>,
+    rules: &Rules,
+) -> Result<Stones<C>, SolveError> {
+    let mut new_stones = HashMap::with_capacity(stones.len());
+    for (number, count) in stones.iter() {
+        for new_number in rules.apply(*number)? {
+            add(&mut new_stones, new_number, count)?;
         }
+    }
+    Ok(new_stones)
+}
 
-        stones = new_stones;
+/// Total number of stones in `stones`.
+pub fn total<C: Count>(stones: &Stones<C>) -> Result<Option<C>, SolveError> {
+    let mut counts = stones.values();
+    let Some(first) = counts.next() else {
+        return Ok(None);
+    };
+
+    counts
+        .try_fold(first.clone(), |total, count| total.checked_add(count))
+        .map(Some)
+        .ok_or_else(|| SolveError::Overflow("the total number of stones".to_string()))
+}
+
+/// Number of stones after `blinks` blinks, `None` when there are no stones left.
+pub fn count<C: Count>(
+    init_stones: &[Stone],
+    blinks: usize,
+    rules: &Rules,
+) -> Result<Option<C>, SolveError> {
+    let mut stones = initial(init_stones)?;
+    for _ in 0..blinks {
+        stones = blink(&stones, rules)?;
     }
+    total(&stones)
+}
 
-    println!(
-        "{}: number of stones: '{}'",
-        name,
-        stones.values().sum::<usize>()
-    )
+/// Stones after one blink, blink 0 being the initial stones.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlinkStats<C> {
+    pub blink: usize,
+    pub total: C,
+    pub distinct: usize,
+    /// Number of distinct values seen since the initial stones.
+    pub seen: usize,
+    pub largest: u64,
 }
 
-pub fn run() {
-    solve("First Example", include_str!("data/day11/ex1"), 25);
-    solve("First", include_str!("data/day11/input"), 25);
-    solve("Second Example", include_str!("data/day11/ex1"), 75);
-    solve("Second", include_str!("data/day11/input"), 75);
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Statistics<C> {
+    pub blinks: Vec<BlinkStats<C>>,
+    /// First blink bringing no value that was not seen before. Every value after it has already
+    /// been seen too, as the values seen before it were all replaced by values seen at most at
+    /// that blink.
+    pub closed_at: Option<usize>,
+}
+
+impl<C: Count> Statistics<C> {
+    /// Counts and largest value at each blink, as CSV.
+    pub fn csv(&self) -> String {
+        csv::table(
+            ["blink", "total", "distinct", "seen", "largest"],
+            self.blinks.iter().map(|stats| {
+                [
+                    stats.blink.to_string(),
+                    stats.total.to_string(),
+                    stats.distinct.to_string(),
+                    stats.seen.to_string(),
+                    stats.largest.to_string(),
+                ]
+            }),
+        )
+    }
+}
+
+/// Statistics of the stones at each of `blinks` blinks.
+pub fn statistics<C: Count + Default>(
+    init_stones: &[Stone],
+    blinks: usize,
+    rules: &Rules,
+) -> Result<Statistics<C>, SolveError> {
+    let mut stones = initial(init_stones)?;
+    let mut seen = HashSet::new();
+    let mut statistics = Statistics {
+        blinks: Vec::with_capacity(blinks + 1),
+        closed_at: None,
+    };
+
+    for index in 0..=blinks {
+        if index > 0 {
+            stones = blink(&stones, rules)?;
+        }
+
+        let num_seen = seen.len();
+        seen.extend(stones.keys().copied());
+        if index > 0 && seen.len() == num_seen && statistics.closed_at.is_none() {
+            statistics.closed_at = Some(index);
+        }
+
+        statistics.blinks.push(BlinkStats {
+            blink: index,
+            total: total(&stones)?.unwrap_or_default(),
+            distinct: stones.len(),
+            seen: seen.len(),
+            largest: stones.keys().max().copied().unwrap_or_default(),
+        });
+    }
+
+    Ok(statistics)
+}
+
+fn parse(input: &str) -> IResult<&str, Vec<Stone>> {
+    all_consuming(terminated(
+        separated_list1(space1, map(u64, |number| Stone { number })),
+        opt(line_ending),
+    ))(input)
+}
+
+fn solve(
+    init_stones: &[Stone],
+    blinks: usize,
+) -> Result<Answer, SolveError> {
+    let count = count::<u64>(init_stones, blinks, &Rules::standard())?;
+    Ok(count.unwrap_or(0).into())
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Stone>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        solve(input, 25)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        solve(input, 75)
+    }
 }
@@ -1,206 +1,572 @@
-use std::collections::HashSet;
-
-const TRANSLATIONS: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
-
-struct Puzzle {
-    gardens: Vec<Vec<char>>,
-    num_rows: i32,
-    num_cols: i32,
+use crate::{
+    ansi::{
+        paint,
+        BLUE,
+        BOLD,
+        CYAN,
+        GREEN,
+        MAGENTA,
+        RED,
+        YELLOW,
+    },
+    answer::Answer,
+    error::{
+        ParseError,
+        SolveError,
+    },
+    grid::{
+        parse_chars,
+        Direction,
+        Grid,
+        Point,
+    },
+    solution::Solution,
+};
+use std::fmt::Write;
+
+/// Disjoint sets of cells, identified by their row-major index.
+struct UnionFind {
+    parents: Vec<usize>,
 }
 
-impl Puzzle {
-    fn new(gardens: Vec<Vec<char>>) -> Self {
-        let num_rows = gardens.len() as i32;
-        let num_cols = gardens[0].len() as i32;
-
+impl UnionFind {
+    fn new(len: usize) -> Self {
         Self {
-            gardens,
-            num_rows,
-            num_cols,
+            parents: (0..len).collect(),
         }
     }
 
-    fn find_zones(&self) -> Vec<Zone> {
-        let mut all_visited = HashSet::new();
-        let mut zones = Vec::new();
+    fn find(
+        &mut self,
+        mut idx: usize,
+    ) -> usize {
+        while self.parents[idx] != idx {
+            self.parents[idx] = self.parents[self.parents[idx]];
+            idx = self.parents[idx];
+        }
+        idx
+    }
 
-        for (row_idx, row) in self.gardens.iter().enumerate() {
-            for (col_idx, garden) in row.iter().enumerate() {
-                if all_visited.contains(&(row_idx as i32, col_idx as i32)) {
-                    continue;
-                }
+    /// Merges the sets of `a` and `b`, keeping the smallest index as the root.
+    fn union(
+        &mut self,
+        a: usize,
+        b: usize,
+    ) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parents[a.max(b)] = a.min(b);
+        }
+    }
+}
 
-                // Start a new zone
-                let mut visited = HashSet::new();
-                let mut next = HashSet::new();
-                next.insert((row_idx as i32, col_idx as i32));
+/// Corners of a cell, as the two sides meeting there and the diagonal between them.
+const CORNERS: [(Direction, Direction, Direction); 4] = [
+    (Direction::Up, Direction::Left, Direction::UpLeft),
+    (Direction::Up, Direction::Right, Direction::UpRight),
+    (Direction::Down, Direction::Left, Direction::DownLeft),
+    (Direction::Down, Direction::Right, Direction::DownRight),
+];
+
+/// Whether the cell at `point`, in zone `label`, has a fence corner on the given side. Both sides
+/// are fenced for an outer corner, while for an inner corner only the diagonal cell is outside.
+fn is_corner(
+    labels: &Grid<usize>,
+    point: Point,
+    (vertical, horizontal, diagonal): (Direction, Direction, Direction),
+) -> bool {
+    let label = labels.get(point);
+    let same = |direction: Direction| labels.get(point.step(direction)) == label;
+
+    (!same(vertical) && !same(horizontal))
+        || (same(vertical) && same(horizontal) && !same(diagonal))
+}
 
-                loop {
-                    if next.is_empty() {
-                        break;
-                    }
+/// Vertex of the cell at `point` between its `vertical` and `horizontal` sides, vertices being
+/// numbered like cells with one more row and column.
+fn vertex(
+    point: Point,
+    vertical: Direction,
+    horizontal: Direction,
+) -> Point {
+    Point::new(
+        point.row + (vertical == Direction::Down) as i32,
+        point.col + (horizontal == Direction::Right) as i32,
+    )
+}
 
-                    let mut new_next = HashSet::new();
-                    for (row_idx, col_idx) in &next {
-                        new_next.extend(TRANSLATIONS.iter().filter_map(
-                            |(row_delta, col_delta)| {
-                                let row_idx = row_idx + row_delta;
-                                let col_idx = col_idx + col_delta;
-
-                                if row_idx < 0 || row_idx >= self.num_rows {
-                                    return None;
-                                }
-                                if col_idx < 0 || col_idx >= self.num_cols {
-                                    return None;
-                                }
-                                if visited.contains(&(row_idx, col_idx)) {
-                                    return None;
-                                }
-                                if all_visited.contains(&(row_idx, col_idx)) {
-                                    return None;
-                                }
-
-                                let value = self.gardens[row_idx as usize][col_idx as usize];
-                                if value != *garden {
-                                    return None;
-                                }
-
-                                Some((row_idx, col_idx))
-                            },
-                        ));
-                    }
+const SVG_CELL: i32 = 24;
 
-                    visited.extend(next);
+/// Terminal color of a garden.
+fn ansi_color(garden: char) -> &'static str {
+    [RED, GREEN, YELLOW, BLUE, MAGENTA, CYAN][garden as usize % 6]
+}
 
-                    next = new_next;
-                }
+/// SVG color of a garden, spreading consecutive letters around the color wheel.
+fn svg_color(garden: char) -> String {
+    format!("hsl({}, 65%, 75%)", (garden as u32 * 47) % 360)
+}
 
-                zones.push(Zone {
-                    coords: visited.clone(),
-                    garden: *garden,
-                });
-                all_visited.extend(visited);
-            }
-        }
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Zone {
+    garden: char,
+    area: usize,
+    perimeter: usize,
+    corners: usize,
+    min: Point,
+    max: Point,
+}
 
-        zones
+impl Zone {
+    pub fn garden(&self) -> char {
+        self.garden
+    }
+
+    pub fn area(&self) -> usize {
+        self.area
+    }
+
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    /// Number of straight fence sections, which is the number of corners of the zone.
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+
+    /// Top left and bottom right cells of the smallest rectangle holding the zone.
+    pub fn bounds(&self) -> (Point, Point) {
+        (self.min, self.max)
     }
 }
 
-fn parse(data: &str) -> Puzzle {
-    Puzzle::new(data.lines().map(|line| line.chars().collect()).collect())
+/// Holes of a zone, and the zones in them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Enclosure {
+    /// Number of groups of cells of other zones fenced in by the zone.
+    pub holes: usize,
+    /// Part of the perimeter fencing the holes, the rest of it being the outer perimeter.
+    pub inner_perimeter: usize,
+    /// Labels of the zones in the holes, at any depth, in increasing order.
+    pub enclosed: Vec<usize>,
+    /// Label of the innermost zone around this one, its parent in the containment tree.
+    pub parent: Option<usize>,
 }
 
-#[derive(Debug, Clone)]
-struct Zone {
-    garden: char,
-    coords: HashSet<(i32, i32)>,
+/// Zones of the map, with the label of the zone of each cell.
+#[derive(Clone, Debug)]
+pub struct Regions {
+    labels: Grid<usize>,
+    zones: Vec<Zone>,
 }
 
-impl Zone {
-    fn area(&self) -> usize {
-        self.coords.len()
+impl Regions {
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
     }
 
-    fn perimeter_contributors(&self) -> Vec<(i32, i32)> {
-        let mut perimeter_contributors = Vec::new();
+    /// Zones, indexed by label, in the order of their first cell row by row.
+    pub fn zones(&self) -> &[Zone] {
+        &self.zones
+    }
 
-        for (row_idx, col_idx) in &self.coords {
-            for (row_delta, col_delta) in &TRANSLATIONS {
-                let row_idx = row_idx + row_delta;
-                let col_idx = col_idx + col_delta;
+    fn is_fence(
+        &self,
+        point: Point,
+        direction: Direction,
+    ) -> bool {
+        self.labels.get(point) != self.labels.get(point.step(direction))
+    }
 
-                if !self.coords.contains(&(row_idx, col_idx)) {
-                    perimeter_contributors.push((row_idx, col_idx));
+    /// Number of corners counted at each vertex, summed over the zones meeting there.
+    pub fn corners(&self) -> Grid<usize> {
+        let mut corners = Grid::filled(self.labels.num_rows() + 1, self.labels.num_cols() + 1, 0);
+        for point in self.labels.points() {
+            for corner in CORNERS {
+                if is_corner(&self.labels, point, corner) {
+                    corners[vertex(point, corner.0, corner.1)] += 1;
                 }
             }
         }
+        corners
+    }
+
+    /// Holes of the zone `label`, found by filling its bounding box, with a margin, from the
+    /// margin: cells of other zones that the fill does not reach are fenced in.
+    fn enclosure(
+        &self,
+        label: usize,
+    ) -> Enclosure {
+        const UNKNOWN: u8 = 0;
+        const OUTSIDE: u8 = 1;
+        const HOLE: u8 = 2;
+
+        let zone = &self.zones[label];
+        let origin = zone.min - Point::new(1, 1);
+        let mut states = Grid::filled(
+            (zone.max.row - zone.min.row + 3) as usize,
+            (zone.max.col - zone.min.col + 3) as usize,
+            UNKNOWN,
+        );
+        let in_zone = |local: Point| self.labels.get(local + origin) == Some(&label);
+
+        let fill = |states: &mut Grid<u8>, start: Point, state: u8| {
+            let mut filled = Vec::new();
+            let mut stack = vec![start];
+            states[start] = state;
+            while let Some(point) = stack.pop() {
+                filled.push(point);
+                for neighbour in states.neighbours(point).collect::<Vec<_>>() {
+                    if states[neighbour] == UNKNOWN && !in_zone(neighbour) {
+                        states[neighbour] = state;
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            filled
+        };
+
+        let margin: Vec<Point> = states
+            .points()
+            .filter(|point| {
+                point.row == 0
+                    || point.col == 0
+                    || point.row as usize == states.num_rows() - 1
+                    || point.col as usize == states.num_cols() - 1
+            })
+            .collect();
+        for point in margin {
+            if states[point] == UNKNOWN {
+                fill(&mut states, point, OUTSIDE);
+            }
+        }
+
+        let mut enclosure = Enclosure::default();
+        for point in states.points().collect::<Vec<_>>() {
+            if states[point] == UNKNOWN && !in_zone(point) {
+                enclosure.holes += 1;
+                enclosure.enclosed.extend(
+                    fill(&mut states, point, HOLE)
+                        .into_iter()
+                        .map(|point| self.labels[point + origin]),
+                );
+            }
+        }
+        enclosure.enclosed.sort_unstable();
+        enclosure.enclosed.dedup();
 
-        perimeter_contributors
+        enclosure.inner_perimeter = states
+            .points()
+            .filter(|point| in_zone(*point))
+            .flat_map(|point| states.neighbours(point).collect::<Vec<_>>())
+            .filter(|neighbour| states[*neighbour] == HOLE)
+            .count();
+
+        enclosure
     }
 
-    fn perimeter(&self) -> usize {
-        self.perimeter_contributors().len()
+    /// Holes and containment tree of every zone, indexed by label.
+    pub fn enclosures(&self) -> Vec<Enclosure> {
+        let mut enclosures: Vec<Enclosure> = (0..self.zones.len())
+            .map(|label| self.enclosure(label))
+            .collect();
+
+        // Zones around a zone are nested in one another, the innermost one being the deepest.
+        let mut depths = vec![0; self.zones.len()];
+        for enclosure in enclosures.iter() {
+            for label in enclosure.enclosed.iter() {
+                depths[*label] += 1;
+            }
+        }
+        for outer in 0..enclosures.len() {
+            for inner in enclosures[outer].enclosed.clone() {
+                let parent = &mut enclosures[inner].parent;
+                if parent.is_none_or(|parent| depths[parent] < depths[outer]) {
+                    *parent = Some(outer);
+                }
+            }
+        }
+
+        enclosures
     }
 
-    fn sides(&self) -> usize {
-        let perimeter = self.perimeter_contributors();
+    fn first_cells(&self) -> Vec<Point> {
+        let mut first_cells = vec![None; self.zones.len()];
+        for (point, label) in self.labels.iter() {
+            first_cells[*label].get_or_insert(point);
+        }
+        first_cells.into_iter().flatten().collect()
+    }
 
-        let mut corners = Vec::new();
-        for (r, c) in self.coords.iter().copied() {
-            // Top left corner
-            if (perimeter.contains(&(r - 1, c)) && perimeter.contains(&(r, c - 1)))
-                || (perimeter.contains(&(r - 1, c - 1))
-                    && self.coords.contains(&(r - 1, c))
-                    && self.coords.contains(&(r, c - 1)))
-            {
-                corners.push((r - 1, c - 1));
+    /// Map drawn with fences between zones, counted corners as `+` (or their number when several
+    /// zones have one at the same vertex), followed by the measures of each zone.
+    pub fn ansi(&self) -> String {
+        let corners = self.corners();
+        let mut output = String::new();
+
+        for row in 0..=self.labels.num_rows() as i32 * 2 {
+            for col in 0..=self.labels.num_cols() as i32 * 2 {
+                let cell = Point::new((row - 1).div_euclid(2), (col - 1).div_euclid(2));
+                let c = match (row % 2, col % 2) {
+                    (1, 1) => {
+                        let zone = &self.zones[self.labels[cell]];
+                        paint(zone.garden, &[ansi_color(zone.garden)])
+                    }
+                    (0, 1) if self.is_fence(cell, Direction::Down) => "-".to_string(),
+                    (1, 0) if self.is_fence(cell, Direction::Right) => "|".to_string(),
+                    (0, 0) => {
+                        let vertex = Point::new(row / 2, col / 2);
+                        let (up_left, down_right) = (vertex - Point::new(1, 1), vertex);
+                        let horizontal = self.is_fence(up_left, Direction::Down)
+                            || self.is_fence(Point::new(up_left.row, vertex.col), Direction::Down);
+                        let vertical = self.is_fence(up_left, Direction::Right)
+                            || self.is_fence(
+                                Point::new(down_right.row, up_left.col),
+                                Direction::Right,
+                            );
+
+                        match corners[vertex] {
+                            0 if horizontal => "-".to_string(),
+                            0 if vertical => "|".to_string(),
+                            0 => " ".to_string(),
+                            1 => paint('+', &[BOLD]),
+                            count => paint(count, &[BOLD]),
+                        }
+                    }
+                    _ => " ".to_string(),
+                };
+                output.push_str(&c);
             }
+            output.push('\n');
+        }
 
-            // Top right corner
-            if (perimeter.contains(&(r - 1, c)) && perimeter.contains(&(r, c + 1)))
-                || (perimeter.contains(&(r - 1, c + 1))
-                    && self.coords.contains(&(r - 1, c))
-                    && self.coords.contains(&(r, c + 1)))
-            {
-                corners.push((r - 1, c + 1));
+        let first_cells = self.first_cells();
+        let enclosures = self.enclosures();
+        for (label, first_cell) in first_cells.iter().enumerate() {
+            let zone = &self.zones[label];
+            let enclosure = &enclosures[label];
+            let _ = write!(
+                output,
+                "{} at {}: area {}, perimeter {} ({} outer, {} inner), sides {}, holes {}, bounds {} to {}",
+                paint(zone.garden, &[ansi_color(zone.garden)]),
+                first_cell,
+                zone.area,
+                zone.perimeter,
+                zone.perimeter - enclosure.inner_perimeter,
+                enclosure.inner_perimeter,
+                zone.corners,
+                enclosure.holes,
+                zone.min,
+                zone.max
+            );
+            if let Some(parent) = enclosure.parent {
+                let _ = write!(
+                    output,
+                    ", inside {} at {}",
+                    self.zones[parent].garden, first_cells[parent]
+                );
             }
+            output.push('\n');
+        }
+
+        output
+    }
 
-            // Bottom left corner
-            if (perimeter.contains(&(r + 1, c)) && perimeter.contains(&(r, c - 1)))
-                || (perimeter.contains(&(r + 1, c - 1))
-                    && self.coords.contains(&(r + 1, c))
-                    && self.coords.contains(&(r, c - 1)))
-            {
-                corners.push((r + 1, c - 1));
+    /// Map with cells colored by garden, fences between zones, a dot on every counted corner and
+    /// the measures of each zone written on its first cell.
+    pub fn svg(&self) -> String {
+        let width = self.labels.num_cols() as i32 * SVG_CELL;
+        let height = self.labels.num_rows() as i32 * SVG_CELL;
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 {} {}" font-family="monospace" font-size="{}">"#,
+            width + 4,
+            height + 4,
+            SVG_CELL / 3
+        );
+
+        for (point, label) in self.labels.iter() {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                point.col * SVG_CELL,
+                point.row * SVG_CELL,
+                SVG_CELL,
+                SVG_CELL,
+                svg_color(self.zones[*label].garden)
+            );
+        }
+
+        for point in self.labels.points() {
+            let (x, y) = (point.col * SVG_CELL, point.row * SVG_CELL);
+            let sides = [
+                (Direction::Up, (x, y, x + SVG_CELL, y)),
+                (Direction::Left, (x, y, x, y + SVG_CELL)),
+                (
+                    Direction::Down,
+                    (x, y + SVG_CELL, x + SVG_CELL, y + SVG_CELL),
+                ),
+                (
+                    Direction::Right,
+                    (x + SVG_CELL, y, x + SVG_CELL, y + SVG_CELL),
+                ),
+            ];
+            for (direction, (x1, y1, x2, y2)) in sides {
+                // Fences between two cells are drawn from the upper or left one only.
+                let inside = self.labels.contains(point.step(direction));
+                if self.is_fence(point, direction)
+                    && !(inside && matches!(direction, Direction::Up | Direction::Left))
+                {
+                    let _ = writeln!(
+                        svg,
+                        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black" stroke-width="2"/>"#,
+                        x1, y1, x2, y2
+                    );
+                }
             }
+        }
 
-            // Bottom right corner
-            if (perimeter.contains(&(r + 1, c)) && perimeter.contains(&(r, c + 1)))
-                || (perimeter.contains(&(r + 1, c + 1))
-                    && self.coords.contains(&(r + 1, c))
-                    && self.coords.contains(&(r, c + 1)))
-            {
-                corners.push((r + 1, c + 1));
+        for (vertex, count) in self.corners().iter() {
+            if *count > 0 {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="red"><title>{} corner(s)</title></circle>"#,
+                    vertex.col * SVG_CELL,
+                    vertex.row * SVG_CELL,
+                    2 + count,
+                    count
+                );
             }
         }
 
-        corners.len()
+        for (label, first_cell) in self.first_cells().into_iter().enumerate() {
+            let zone = &self.zones[label];
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}"><title>{}: area {}, perimeter {}, sides {}</title>{} {}/{}/{}</text>"#,
+                first_cell.col * SVG_CELL + 3,
+                first_cell.row * SVG_CELL + SVG_CELL / 2,
+                zone.garden,
+                zone.area,
+                zone.perimeter,
+                zone.corners,
+                zone.garden,
+                zone.area,
+                zone.perimeter,
+                zone.corners
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
     }
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let data = parse(data);
-    let zones = data.find_zones();
-
-    let score = zones
-        .iter()
-        .map(|zone| zone.area() * zone.perimeter())
-        .sum::<usize>();
-    println!("{}: {:#?}", name, score);
+pub struct Puzzle {
+    gardens: Grid<char>,
 }
 
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let data = parse(data);
-    let zones = data.find_zones();
-
-    let score = zones
-        .iter()
-        .map(|zone| zone.area() * zone.sides())
-        .sum::<usize>();
-    println!("{}: {:#?}", name, score);
+impl Puzzle {
+    fn new(gardens: Grid<char>) -> Self {
+        Self { gardens }
+    }
+
+    /// Labels the zones by merging each cell with its left and upper neighbours, then measures
+    /// them all in a single sweep over the cells.
+    pub fn regions(&self) -> Regions {
+        let num_cols = self.gardens.num_cols();
+        let index = |point: Point| point.row as usize * num_cols + point.col as usize;
+
+        let mut sets = UnionFind::new(self.gardens.num_rows() * num_cols);
+        for (point, garden) in self.gardens.iter() {
+            for direction in [Direction::Left, Direction::Up] {
+                let neighbour = point.step(direction);
+                if self.gardens.get(neighbour) == Some(garden) {
+                    sets.union(index(point), index(neighbour));
+                }
+            }
+        }
+
+        // Roots are the first cell of their zone, so they are met before the rest of it.
+        let mut root_labels = vec![usize::MAX; sets.parents.len()];
+        let mut zones = Vec::new();
+        let labels = Grid::new(
+            self.gardens.num_rows(),
+            num_cols,
+            self.gardens
+                .iter()
+                .map(|(point, garden)| {
+                    let root = sets.find(index(point));
+                    if root_labels[root] == usize::MAX {
+                        root_labels[root] = zones.len();
+                        zones.push(Zone {
+                            garden: *garden,
+                            area: 0,
+                            perimeter: 0,
+                            corners: 0,
+                            min: point,
+                            max: point,
+                        });
+                    }
+                    root_labels[root]
+                })
+                .collect(),
+        );
+
+        for (point, label) in labels.iter() {
+            let zone = &mut zones[*label];
+
+            zone.area += 1;
+            zone.min = Point::new(zone.min.row.min(point.row), zone.min.col.min(point.col));
+            zone.max = Point::new(zone.max.row.max(point.row), zone.max.col.max(point.col));
+            zone.perimeter += Direction::ORTHOGONAL
+                .iter()
+                .filter(|direction| labels.get(point.step(**direction)) != Some(label))
+                .count();
+            zone.corners += CORNERS
+                .iter()
+                .filter(|corner| is_corner(&labels, point, **corner))
+                .count();
+        }
+
+        Regions { labels, zones }
+    }
 }
 
-pub fn run() {
-    first("First example", include_str!("data/day12/ex1"));
-    first("First", include_str!("data/day12/input"));
-    second("Second example", include_str!("data/day12/ex1")); // 1206
-    second("Second example 2", include_str!("data/day12/ex2")); // 368
-    second("Second", include_str!("data/day12/input"));
+fn parse(data: &str) -> Result<Puzzle, ParseError> {
+    parse_chars(data, Some, "a garden").map(Puzzle::new)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Puzzle;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let regions = input.regions();
+
+        let score = regions
+            .zones()
+            .iter()
+            .map(|zone| zone.area() * zone.perimeter())
+            .sum::<usize>();
+        Ok(score.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let regions = input.regions();
+
+        let score = regions
+            .zones()
+            .iter()
+            .map(|zone| zone.area() * zone.sides())
+            .sum::<usize>();
+        Ok(score.into())
+    }
 }
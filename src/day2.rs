@@ -1,3 +1,12 @@
+use crate::{
+    answer::Answer,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    solution::Solution,
+};
 use itertools::Itertools;
 use nom::{
     character::complete::{
@@ -13,7 +22,6 @@ use nom::{
         separated_list1,
     },
     sequence::terminated,
-    Finish,
     IResult,
 };
 
@@ -45,44 +53,38 @@ fn is_safe(level: &[i32]) -> bool {
     true
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let (_, data) = parse(data).finish().unwrap();
-    let safe = data.iter().filter(|level| is_safe(level)).count();
-    println!("{}: Safe levels: '{:?}'", name, safe);
-}
+pub struct Day2;
 
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let (_, data) = parse(data).finish().unwrap();
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
 
-    let mut safe_levels = 0;
-    for level in data.iter() {
-        if is_safe(level) {
-            safe_levels += 1;
-        } else {
-            // Retry removing one element every time
-            for (idx, _) in level.iter().enumerate() {
-                let mut new_level = level.to_vec();
-                new_level.remove(idx);
-                if is_safe(&new_level) {
-                    safe_levels += 1;
-                    break;
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let safe = input.iter().filter(|level| is_safe(level)).count();
+        Ok(safe.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let mut safe_levels = 0;
+        for level in input.iter() {
+            if is_safe(level) {
+                safe_levels += 1;
+            } else {
+                // Retry removing one element every time
+                for (idx, _) in level.iter().enumerate() {
+                    let mut new_level = level.to_vec();
+                    new_level.remove(idx);
+                    if is_safe(&new_level) {
+                        safe_levels += 1;
+                        break;
+                    }
                 }
             }
         }
-    }
 
-    println!("{}: Safe levels: '{:?}'", name, safe_levels);
-}
-
-pub fn run() {
-    first("First example", include_str!("data/day2/ex1"));
-    first("First", include_str!("data/day2/input"));
-    second("Second example", include_str!("data/day2/ex1"));
-    second("Second", include_str!("data/day2/input"));
+        Ok(safe_levels.into())
+    }
 }
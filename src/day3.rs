@@ -1,3 +1,12 @@
+use crate::{
+    answer::Answer,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    solution::Solution,
+};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -9,24 +18,23 @@ use nom::{
     },
     multi::many1,
     sequence::tuple,
-    Finish,
     IResult,
 };
 
 #[derive(Clone, Debug)]
-struct Mul {
+pub struct Mul {
     left: i32,
     right: i32,
 }
 
 #[derive(Copy, Clone, Debug)]
-enum Instruction {
+pub enum Instruction {
     Do,
     DoNot,
 }
 
 #[derive(Clone, Debug)]
-enum Value {
+pub enum Value {
     Garbage,
     Mul(Mul),
     Instruction(Instruction),
@@ -51,51 +59,46 @@ fn parse(input: &str) -> IResult<&str, Vec<Value>> {
     ))))(input)
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let (_, data) = parse(data).finish().unwrap();
-    let result: i32 = data
-        .iter()
-        .map(|value| {
-            match value {
-                Value::Garbage => 0,
-                Value::Mul(mul) => mul.left * mul.right,
-                Value::Instruction(_) => 0,
-            }
-        })
-        .sum();
-    println!("{}: Result is '{:#?}'", name, result);
-}
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Value>;
 
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let (_, data) = parse(data).finish().unwrap();
-    let mut result = 0;
-    let mut instruction = Instruction::Do;
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
 
-    for value in data.iter() {
-        match value {
-            Value::Garbage => (),
-            Value::Mul(mul) => {
-                match instruction {
-                    Instruction::Do => result += mul.left * mul.right,
-                    Instruction::DoNot => (),
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let result: i32 = input
+            .iter()
+            .map(|value| {
+                match value {
+                    Value::Garbage => 0,
+                    Value::Mul(mul) => mul.left * mul.right,
+                    Value::Instruction(_) => 0,
                 }
-            }
-            Value::Instruction(new_instruction) => instruction = *new_instruction,
-        }
+            })
+            .sum();
+        Ok(result.into())
     }
 
-    println!("{}: Result is '{:#?}'", name, result);
-}
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let mut result = 0;
+        let mut instruction = Instruction::Do;
+
+        for value in input.iter() {
+            match value {
+                Value::Garbage => (),
+                Value::Mul(mul) => {
+                    match instruction {
+                        Instruction::Do => result += mul.left * mul.right,
+                        Instruction::DoNot => (),
+                    }
+                }
+                Value::Instruction(new_instruction) => instruction = *new_instruction,
+            }
+        }
 
-pub fn run() {
-    first("First Example", include_str!("data/day3/ex1"));
-    first("First", include_str!("data/day3/input"));
-    second("Second Example", include_str!("data/day3/ex2"));
-    second("Second", include_str!("data/day3/input"));
+        Ok(result.into())
+    }
 }
@@ -1,3 +1,12 @@
+use crate::{
+    answer::Answer,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    solution::Solution,
+};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
@@ -15,7 +24,6 @@ use nom::{
         separated_pair,
         terminated,
     },
-    Finish,
     IResult,
 };
 use std::collections::{
@@ -30,7 +38,7 @@ struct OrderingRule {
 }
 
 #[derive(Clone, Debug)]
-struct Puzzle {
+pub struct Puzzle {
     left_to_right: HashMap<i32, HashSet<i32>>,
     updates: Vec<Vec<i32>>,
 }
@@ -120,62 +128,55 @@ fn sum_middle_value(updates: &[Vec<i32>]) -> i32 {
     updates.iter().map(|update| update[update.len() / 2]).sum()
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let (_, puzzle) = parse(data).finish().unwrap();
+pub struct Day5;
 
-    let Updates { safe, .. } = puzzle.partition_updates();
-    let sum_middle = sum_middle_value(&safe);
+impl Solution for Day5 {
+    type Input = Puzzle;
 
-    println!("{}: Sum of middle pages is '{:#?}'", name, sum_middle);
-}
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
 
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let (_, puzzle) = parse(data).finish().unwrap();
-
-    let Updates { not_safe, .. } = puzzle.partition_updates();
-
-    let mut fixed = Vec::new();
-    for update in not_safe.iter() {
-        let sorted = update
-            .iter()
-            .copied()
-            .sorted_by(|a, b| {
-                if puzzle
-                    .left_to_right
-                    .get(b)
-                    .is_some_and(|set| set.contains(a))
-                {
-                    // Check if there is a rule stating that b is before a
-                    std::cmp::Ordering::Greater
-                } else if puzzle
-                    .left_to_right
-                    .get(a)
-                    .is_some_and(|set| set.contains(b))
-                {
-                    // Check is there is a rule stating that a is before b
-                    std::cmp::Ordering::Less
-                } else {
-                    std::cmp::Ordering::Equal
-                }
-            })
-            .collect::<Vec<_>>();
-        fixed.push(sorted);
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let Updates { safe, .. } = input.partition_updates();
+        let sum_middle = sum_middle_value(&safe);
+
+        Ok(sum_middle.into())
     }
 
-    let sum_middle = sum_middle_value(&fixed);
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let Updates { not_safe, .. } = input.partition_updates();
+
+        let mut fixed = Vec::new();
+        for update in not_safe.iter() {
+            let sorted = update
+                .iter()
+                .copied()
+                .sorted_by(|a, b| {
+                    if input
+                        .left_to_right
+                        .get(b)
+                        .is_some_and(|set| set.contains(a))
+                    {
+                        // Check if there is a rule stating that b is before a
+                        std::cmp::Ordering::Greater
+                    } else if input
+                        .left_to_right
+                        .get(a)
+                        .is_some_and(|set| set.contains(b))
+                    {
+                        // Check is there is a rule stating that a is before b
+                        std::cmp::Ordering::Less
+                    } else {
+                        std::cmp::Ordering::Equal
+                    }
+                })
+                .collect::<Vec<_>>();
+            fixed.push(sorted);
+        }
 
-    println!("{}: Sum of middle pages is '{:#?}'", name, sum_middle);
-}
+        let sum_middle = sum_middle_value(&fixed);
 
-pub fn run() {
-    first("First Example", include_str!("data/day5/ex1"));
-    first("First", include_str!("data/day5/input"));
-    second("Second Example", include_str!("data/day5/ex1"));
-    second("Second", include_str!("data/day5/input"));
+        Ok(sum_middle.into())
+    }
 }
@@ -1,37 +1,45 @@
+use crate::{
+    ansi::{
+        paint,
+        BOLD,
+        CYAN,
+        DIM,
+        MAGENTA,
+        RED,
+        YELLOW,
+    },
+    answer::Answer,
+    csv,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    grid::{
+        grid,
+        Direction,
+        Grid,
+        Point,
+    },
+    solution::Solution,
+};
 use nom::{
     branch::alt,
-    character::complete::{
-        char,
-        line_ending,
-    },
+    character::complete::char,
     combinator::{
         all_consuming,
         map,
-        opt,
         value,
     },
-    multi::many1,
-    sequence::terminated,
-    Finish,
     IResult,
 };
 use rayon::prelude::*;
+use serde::Serialize;
 use std::{
-    collections::{
-        HashMap,
-        HashSet,
-    },
+    collections::HashSet,
     fmt::Display,
 };
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum Value {
     Obstruction,
@@ -39,123 +47,436 @@ enum Value {
     Empty,
 }
 
-#[derive(Clone, Debug)]
-struct Puzzle {
-    values: Vec<Vec<Value>>,
-    visited: HashMap<(usize, usize), HashSet<Direction>>,
+/// Position and heading of the guard.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Guard {
+    pub position: Point,
+    pub direction: Direction,
+}
+
+/// Where a straight walk of the guard ends.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// In front of an obstruction, where the guard turns right.
+    Turn(Point),
+    /// On the last cell before leaving the map.
+    Exit(Point),
+}
+
+impl Stop {
+    pub fn point(self) -> Point {
+        match self {
+            Stop::Turn(point) | Stop::Exit(point) => point,
+        }
+    }
+}
+
+/// Straight walk of the guard between two turns.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Leg {
+    pub start: Guard,
+    pub stop: Stop,
+}
+
+impl Leg {
+    /// Cells walked on, from the start of the leg to its stop included.
+    pub fn cells(&self) -> impl Iterator<Item = Point> {
+        let delta = self.start.direction.delta();
+        let end = self.stop.point();
+        std::iter::successors(Some(self.start.position), move |point| {
+            (*point != end).then_some(*point + delta)
+        })
+    }
+}
+
+/// Loop the guard walks in when an extra obstruction is added to the map.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Loop {
+    pub obstruction: Point,
+    /// Number of cells walked to go around the loop once.
+    pub length: usize,
+    /// First position and heading of the patrol that is part of the loop.
+    pub entry: Guard,
+}
+
+/// Obstruction, length and entry of each loop, as CSV.
+pub fn loops_csv(loops: &[Loop]) -> String {
+    csv::table(
+        [
+            "obstruction_row",
+            "obstruction_col",
+            "length",
+            "entry_row",
+            "entry_col",
+            "entry_direction",
+        ],
+        loops.iter().map(|item| {
+            [
+                item.obstruction.row.to_string(),
+                item.obstruction.col.to_string(),
+                item.length.to_string(),
+                item.entry.position.row.to_string(),
+                item.entry.position.col.to_string(),
+                format!("{:?}", item.entry.direction),
+            ]
+        }),
+    )
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Outcome {
+pub enum Outcome {
     Loop,
     OutOfBounds,
-    Continue,
+}
+
+fn arrow(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '^',
+        Direction::Left => '<',
+        Direction::Right => '>',
+        Direction::Down => 'V',
+        _ => '?',
+    }
+}
+
+fn index(direction: Direction) -> usize {
+    match direction {
+        Direction::Up => 0,
+        Direction::Right => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        _ => unreachable!("the guard only moves orthogonally"),
+    }
+}
+
+/// Number of steps from `from` to `to` when walking in `direction`, if `to` is ahead of `from`.
+fn distance(
+    from: Point,
+    to: Point,
+    direction: Direction,
+) -> Option<i32> {
+    let offset = to - from;
+    let delta = direction.delta();
+    let steps = if delta.row == 0 {
+        (offset.row == 0).then_some(offset.col * delta.col)
+    } else {
+        (offset.col == 0).then_some(offset.row * delta.row)
+    };
+
+    steps.filter(|steps| *steps >= 0)
+}
+
+/// For every cell, where the guard walking from it in `direction` stops.
+fn stops(
+    obstructions: &Grid<bool>,
+    direction: Direction,
+) -> Grid<Stop> {
+    let mut stops = Grid::filled(
+        obstructions.num_rows(),
+        obstructions.num_cols(),
+        Stop::Exit(Point::default()),
+    );
+
+    // The cell ahead must be filled in before the current one.
+    let mut points: Vec<Point> = obstructions.points().collect();
+    if matches!(direction, Direction::Down | Direction::Right) {
+        points.reverse();
+    }
+
+    for point in points {
+        let ahead = point.step(direction);
+        stops[point] = match obstructions.get(ahead) {
+            None => Stop::Exit(point),
+            Some(true) => Stop::Turn(point),
+            Some(false) => stops[ahead],
+        };
+    }
+
+    stops
+}
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    obstructions: Grid<bool>,
+    guard: Option<Guard>,
+    /// Stops of each cell, indexed by direction.
+    stops: [Grid<Stop>; 4],
 }
 
 impl Puzzle {
-    fn new(values: Vec<Vec<Value>>) -> Self {
+    fn new(values: Grid<Value>) -> Self {
+        let guard = values.iter().find_map(|(position, value)| {
+            match value {
+                Value::Guard(direction) => {
+                    Some(Guard {
+                        position,
+                        direction: *direction,
+                    })
+                }
+                _ => None,
+            }
+        });
+        let obstructions = values.map(|value| *value == Value::Obstruction);
+        let stops = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ]
+        .map(|direction| stops(&obstructions, direction));
+
         Self {
-            values,
-            visited: HashMap::new(),
+            obstructions,
+            guard,
+            stops,
         }
     }
 
-    // Returns true if there is at least one guard in the puzzle.
-    fn move_guard(&mut self) -> Outcome {
-        let mut outcome = Outcome::Continue;
-        let guard = self.guard();
-
-        if let Some(((row_idx, col_idx), direction)) = guard {
-            if self
-                .visited
-                .entry((row_idx, col_idx))
-                .or_default()
-                .contains(&direction)
-            {
-                outcome = Outcome::Loop;
-            } else {
-                self.visited
-                    .entry((row_idx, col_idx))
-                    .or_default()
-                    .insert(direction);
-                if let Some((new_row_idx, new_col_idx)) = self.next(row_idx, col_idx, direction) {
-                    let next_value = self.values[new_row_idx][new_col_idx];
-
-                    if next_value == Value::Obstruction {
-                        // Turn right
-                        let new_direction = match direction {
-                            Direction::Up => Direction::Right,
-                            Direction::Down => Direction::Left,
-                            Direction::Left => Direction::Up,
-                            Direction::Right => Direction::Down,
-                        };
-
-                        self.values[row_idx][col_idx] = Value::Guard(new_direction);
-                    } else {
-                        self.values[new_row_idx][new_col_idx] = Value::Guard(direction);
-                        self.values[row_idx][col_idx] = Value::Empty;
+    pub fn guard(&self) -> Result<Guard, SolveError> {
+        self.guard
+            .ok_or_else(|| SolveError::Unsolvable("there is no guard on the map".to_string()))
+    }
+
+    /// Fails unless `point` is an empty cell of the map other than the start of `guard`, where an
+    /// extra obstruction can go.
+    pub fn check_obstruction(
+        &self,
+        guard: Guard,
+        point: Point,
+    ) -> Result<(), String> {
+        match self.obstructions.get(point) {
+            None => Err(format!("{} is off the map", point)),
+            Some(true) => Err(format!("{} already holds an obstruction", point)),
+            Some(false) if point == guard.position => {
+                Err(format!("{} is where the guard starts", point))
+            }
+            Some(false) => Ok(()),
+        }
+    }
+
+    /// Where the guard stops when walking straight from `guard`, with an optional `extra`
+    /// obstruction added to the map.
+    pub fn walk(
+        &self,
+        guard: Guard,
+        extra: Option<Point>,
+    ) -> Stop {
+        let stop = self.stops[index(guard.direction)][guard.position];
+
+        let blocked = extra.and_then(|extra| {
+            let steps = distance(guard.position, extra, guard.direction)?;
+            let limit = distance(guard.position, stop.point(), guard.direction)?;
+            (steps >= 1 && steps <= limit + 1).then(|| Stop::Turn(extra - guard.direction.delta()))
+        });
+
+        blocked.unwrap_or(stop)
+    }
+
+    /// Runs the patrol leg by leg until the guard leaves the map or walks in a loop, with an
+    /// optional `extra` obstruction added to the map.
+    pub fn run(
+        &self,
+        guard: Guard,
+        extra: Option<Point>,
+        mut on_leg: impl FnMut(Leg),
+    ) -> Outcome {
+        let mut starts = HashSet::new();
+        let mut start = guard;
+
+        loop {
+            if !starts.insert(start) {
+                return Outcome::Loop;
+            }
+
+            let stop = self.walk(start, extra);
+            on_leg(Leg { start, stop });
+
+            match stop {
+                Stop::Turn(position) => {
+                    start = Guard {
+                        position,
+                        direction: start.direction.turn_right(),
                     }
-                } else {
-                    self.values[row_idx][col_idx] = Value::Empty;
-                    outcome = Outcome::OutOfBounds;
                 }
+                Stop::Exit(_) => return Outcome::OutOfBounds,
             }
         }
+    }
 
-        outcome
+    /// Cells walked on by the guard, with an optional `extra` obstruction added to the map.
+    pub fn visited(
+        &self,
+        guard: Guard,
+        extra: Option<Point>,
+    ) -> Grid<bool> {
+        let mut visited = self.obstructions.map(|_| false);
+        self.run(guard, extra, |leg| {
+            for cell in leg.cells() {
+                visited[cell] = true;
+            }
+        });
+        visited
     }
 
-    fn guard(&self) -> Option<((usize, usize), Direction)> {
-        self.values
+    /// Free cells on the path of the guard where an extra obstruction makes it walk in a loop,
+    /// row by row.
+    pub fn loop_obstructions(
+        &self,
+        guard: Guard,
+    ) -> Vec<Point> {
+        // Obstacles should be on visited spots
+        let candidates = self
+            .visited(guard, None)
             .iter()
-            .enumerate()
-            .flat_map(|(row_idx, row)| {
-                row.iter().enumerate().filter_map(move |(col_idx, value)| {
-                    match value {
-                        Value::Guard(direction) => Some(((row_idx, col_idx), *direction)),
-                        _ => None,
-                    }
-                })
-            })
-            .next()
+            .filter(|(point, visited)| **visited && *point != guard.position)
+            .map(|(point, _)| point)
+            .collect::<Vec<_>>();
+
+        candidates
+            .into_par_iter()
+            .filter(|point| self.run(guard, Some(*point), |_| {}) == Outcome::Loop)
+            .collect()
     }
 
-    fn next(
+    /// Loop walked by the guard with an extra `obstruction`, if any.
+    pub fn loop_with(
         &self,
-        row_idx: usize,
-        col_idx: usize,
-        direction: Direction,
-    ) -> Option<(usize, usize)> {
-        match direction {
-            Direction::Up => {
-                if row_idx == 0 {
-                    None
-                } else {
-                    Some((row_idx - 1, col_idx))
-                }
-            }
-            Direction::Down => {
-                if row_idx == self.values.len() - 1 {
-                    None
-                } else {
-                    Some((row_idx + 1, col_idx))
-                }
-            }
-            Direction::Left => {
-                if col_idx == 0 {
-                    None
-                } else {
-                    Some((row_idx, col_idx - 1))
+        guard: Guard,
+        obstruction: Point,
+    ) -> Option<Loop> {
+        let mut legs = Vec::new();
+        if self.run(guard, Some(obstruction), |leg| legs.push(leg)) != Outcome::Loop {
+            return None;
+        }
+
+        // The patrol stopped before walking again from the start of the last leg's turn.
+        let last = legs.last()?;
+        let repeated = Guard {
+            position: last.stop.point(),
+            direction: last.start.direction.turn_right(),
+        };
+        let first = legs.iter().position(|leg| leg.start == repeated)?;
+
+        let states = |leg: Leg| {
+            leg.cells().map(move |position| {
+                Guard {
+                    position,
+                    direction: leg.start.direction,
                 }
+            })
+        };
+        let cycle: HashSet<Guard> = legs[first..].iter().flat_map(|leg| states(*leg)).collect();
+        let entry = legs
+            .iter()
+            .flat_map(|leg| states(*leg))
+            .find(|state| cycle.contains(state))?;
+        let length = legs[first..]
+            .iter()
+            .map(|leg| leg.cells().count() - 1)
+            .sum();
+
+        Some(Loop {
+            obstruction,
+            length,
+            entry,
+        })
+    }
+
+    /// Loops caused by each of the `loop_obstructions`.
+    pub fn loops(
+        &self,
+        guard: Guard,
+    ) -> Vec<Loop> {
+        self.loop_obstructions(guard)
+            .into_par_iter()
+            .filter_map(|obstruction| self.loop_with(guard, obstruction))
+            .collect()
+    }
+
+    /// The map with the given extra `obstructions` drawn as `O`.
+    pub fn render_with(
+        &self,
+        obstructions: &[Point],
+    ) -> String {
+        let obstructions: HashSet<&Point> = obstructions.iter().collect();
+        self.obstructions.render(|point, obstruction| {
+            match self.guard {
+                Some(guard) if guard.position == point => arrow(guard.direction),
+                _ if obstructions.contains(&point) => 'O',
+                _ if *obstruction => '#',
+                _ => '.',
             }
-            Direction::Right => {
-                if col_idx == self.values[row_idx].len() - 1 {
-                    None
-                } else {
-                    Some((row_idx, col_idx + 1))
-                }
+        })
+    }
+
+    fn frame(
+        &self,
+        step: usize,
+        guard: Guard,
+        visited: &Grid<bool>,
+        turns: &HashSet<Point>,
+        extra: Option<Point>,
+    ) -> String {
+        let mut frame = format!(
+            "step {}: guard at {} facing {:?}\n",
+            step, guard.position, guard.direction
+        );
+
+        for (point, obstruction) in self.obstructions.iter() {
+            let cell = if point == guard.position {
+                paint(arrow(guard.direction), &[BOLD, YELLOW])
+            } else if Some(point) == extra {
+                paint('O', &[BOLD, RED])
+            } else if *obstruction {
+                "#".to_string()
+            } else if turns.contains(&point) {
+                paint('+', &[MAGENTA])
+            } else if visited[point] {
+                paint('X', &[DIM, CYAN])
+            } else {
+                ".".to_string()
+            };
+            frame.push_str(&cell);
+
+            if point.col as usize == self.obstructions.num_cols() - 1 {
+                frame.push('\n');
             }
         }
+
+        frame
+    }
+
+    /// Frames of the patrol walked cell by cell, with an optional `extra` obstruction added to the
+    /// map, and how the patrol ends.
+    pub fn animation(
+        &self,
+        guard: Guard,
+        extra: Option<Point>,
+    ) -> (Outcome, impl Iterator<Item = String> + '_) {
+        let mut legs = Vec::new();
+        let outcome = self.run(guard, extra, |leg| legs.push(leg));
+
+        let mut visited = self.obstructions.map(|_| false);
+        let mut turns = HashSet::new();
+        let frames = legs
+            .into_iter()
+            .flat_map(|leg| leg.cells().map(move |position| (leg, position)))
+            .enumerate()
+            .map(move |(step, (leg, position))| {
+                visited[position] = true;
+                let guard = Guard {
+                    position,
+                    direction: leg.start.direction,
+                };
+                let frame = self.frame(step, guard, &visited, &turns, extra);
+
+                if leg.stop == Stop::Turn(position) {
+                    turns.insert(position);
+                }
+                frame
+            });
+
+        (outcome, frames)
     }
 }
 
@@ -164,110 +485,47 @@ impl Display for Puzzle {
         &self,
         f: &mut std::fmt::Formatter<'_>,
     ) -> std::fmt::Result {
-        for (row_idx, row) in self.values.iter().enumerate() {
-            for (col_idx, value) in row.iter().enumerate() {
-                let c = match value {
-                    Value::Obstruction => '#',
-                    Value::Guard(Direction::Up) => '^',
-                    Value::Guard(Direction::Left) => '<',
-                    Value::Guard(Direction::Right) => '>',
-                    Value::Guard(Direction::Down) => 'V',
-                    Value::Empty => {
-                        if self.visited.contains_key(&(row_idx, col_idx)) {
-                            'X'
-                        } else {
-                            '.'
-                        }
-                    }
-                };
-
-                write!(f, "{}", c)?;
-            }
-
-            writeln!(f)?;
-        }
-
-        Ok(())
+        write!(f, "{}", self.render_with(&[]))
     }
 }
 
 fn parse(input: &str) -> IResult<&str, Puzzle> {
     all_consuming(map(
-        many1(terminated(
-            many1(alt((
-                value(Value::Obstruction, char('#')),
-                value(Value::Guard(Direction::Up), char('^')),
-                value(Value::Guard(Direction::Left), char('<')),
-                value(Value::Guard(Direction::Right), char('>')),
-                value(Value::Guard(Direction::Down), char('V')),
-                value(Value::Empty, char('.')),
-            ))),
-            opt(line_ending),
-        )),
+        grid(alt((
+            value(Value::Obstruction, char('#')),
+            value(Value::Guard(Direction::Up), char('^')),
+            value(Value::Guard(Direction::Left), char('<')),
+            value(Value::Guard(Direction::Right), char('>')),
+            value(Value::Guard(Direction::Down), char('V')),
+            value(Value::Empty, char('.')),
+        ))),
         Puzzle::new,
     ))(input)
 }
 
-fn run_puzzle(puzzle: &mut Puzzle) -> Outcome {
-    loop {
-        // println!("{}\n\n", puzzle);
-        match puzzle.move_guard() {
-            Outcome::Loop => {
-                return Outcome::Loop;
-            }
-            Outcome::OutOfBounds => {
-                return Outcome::OutOfBounds;
-            }
-            Outcome::Continue => {}
-        }
-    }
-}
-
-fn first(
-    name: &str,
-    input: &str,
-) {
-    let (_, mut puzzle) = parse(input).finish().unwrap();
+pub struct Day6;
 
-    run_puzzle(&mut puzzle);
+impl Solution for Day6 {
+    type Input = Puzzle;
 
-    println!("{}: Visited '{}'", name, puzzle.visited.keys().len());
-}
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
 
-fn second(
-    name: &str,
-    input: &str,
-) {
-    let (_, original) = parse(input).finish().unwrap();
-
-    // Obstacles should be on visited spots
-    let candidates = {
-        let mut puzzle = original.clone();
-        run_puzzle(&mut puzzle);
-        puzzle
-            .visited
-            .keys()
-            .to_owned()
-            .cloned()
-            .filter(|coords| original.guard().unwrap().0 != *coords)
-            .collect::<Vec<_>>()
-    };
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        let guard = input.guard()?;
+        let visited = input.visited(guard, None);
 
-    let loops = candidates
-        .par_iter()
-        .filter(|(row_idx, col_idx)| {
-            let mut new_puzzle = original.clone();
-            new_puzzle.values[*row_idx][*col_idx] = Value::Obstruction;
-            run_puzzle(&mut new_puzzle) == Outcome::Loop
-        })
-        .count();
+        Ok(visited
+            .iter()
+            .filter(|(_, visited)| **visited)
+            .count()
+            .into())
+    }
 
-    println!("{}: Loops '{}'", name, loops);
-}
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        let guard = input.guard()?;
 
-pub fn run() {
-    first("First example", include_str!("data/day6/ex1"));
-    first("First", include_str!("data/day6/input"));
-    second("Second example", include_str!("data/day6/ex1"));
-    second("Second", include_str!("data/day6/input"));
+        Ok(input.loop_obstructions(guard).len().into())
+    }
 }
@@ -1,3 +1,15 @@
+use crate::{
+    answer::Answer,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    solution::{
+        Part,
+        Solution,
+    },
+};
 use nom::{
     character::complete::{
         char,
@@ -7,121 +19,562 @@ use nom::{
     combinator::{
         all_consuming,
         map,
+        opt,
     },
     multi::separated_list1,
     sequence::{
         separated_pair,
+        terminated,
         tuple,
     },
-    Finish,
     IResult,
 };
+use std::{
+    fmt::{
+        Debug,
+        Display,
+    },
+    ops::RangeInclusive,
+};
 
 #[derive(Clone, Debug)]
-struct Calibration {
-    result: i64,
-    numbers: Vec<i64>,
+pub struct Calibration {
+    result: u64,
+    numbers: Vec<u64>,
 }
 
-impl Calibration {
-    fn has_matching_combination(
+/// Values the left operand can take for an operator to give some result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Preimage {
+    Nothing,
+    Exact(u64),
+    Within(RangeInclusive<u64>),
+}
+
+/// Operator combining the value so far, on the left, with the next number, written with its
+/// symbol.
+pub trait Operator: Debug + Display + Send + Sync {
+    /// `left` combined with `number`, or `None` when undefined or out of range.
+    fn apply(
         &self,
-        operators: Vec<Operator>,
-    ) -> bool {
-        let mut combinations = Vec::new();
+        left: u64,
+        number: u64,
+    ) -> Option<u64>;
 
-        for (idx, number) in self.numbers.iter().enumerate() {
-            if idx == 0 {
-                combinations.push(*number);
-            } else {
-                combinations = combinations
-                    .iter()
-                    .flat_map(|prev| {
-                        operators.iter().map(move |op| {
-                            match op {
-                                Operator::Add => prev + number,
-                                Operator::Multiply => prev * number,
-                                Operator::Concat => format!("{}{}", prev, number).parse().unwrap(),
-                            }
-                        })
-                    })
-                    .filter(|&num| num <= self.result)
-                    .collect();
+    /// Values of `left` for which `apply(left, number)` gives `result`.
+    fn undo(
+        &self,
+        result: u64,
+        number: u64,
+    ) -> Preimage;
+}
+
+impl From<Option<u64>> for Preimage {
+    fn from(value: Option<u64>) -> Self {
+        value.map_or(Preimage::Nothing, Preimage::Exact)
+    }
+}
+
+/// `+`
+#[derive(Copy, Clone, Debug)]
+pub struct Add;
+
+impl Display for Add {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "+")
+    }
+}
+
+impl Operator for Add {
+    fn apply(
+        &self,
+        left: u64,
+        number: u64,
+    ) -> Option<u64> {
+        left.checked_add(number)
+    }
+
+    fn undo(
+        &self,
+        result: u64,
+        number: u64,
+    ) -> Preimage {
+        result.checked_sub(number).into()
+    }
+}
+
+/// `*`
+#[derive(Copy, Clone, Debug)]
+pub struct Multiply;
+
+impl Display for Multiply {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "*")
+    }
+}
+
+impl Operator for Multiply {
+    fn apply(
+        &self,
+        left: u64,
+        number: u64,
+    ) -> Option<u64> {
+        left.checked_mul(number)
+    }
+
+    fn undo(
+        &self,
+        result: u64,
+        number: u64,
+    ) -> Preimage {
+        match (result, number) {
+            // Multiplying by 0 gives 0 whatever the value before
+            (0, 0) => Preimage::Within(0..=u64::MAX),
+            (_, 0) => Preimage::Nothing,
+            _ => {
+                result
+                    .is_multiple_of(number)
+                    .then(|| result / number)
+                    .into()
             }
         }
+    }
+}
+
+/// `||`, or `||<base>` outside of base 10: the digits of the number, in `base`, appended to those
+/// of the value before.
+#[derive(Copy, Clone, Debug)]
+pub struct Concat {
+    base: u64,
+}
+
+impl Concat {
+    pub const DECIMAL: Concat = Concat { base: 10 };
+
+    pub fn new(base: u64) -> Result<Self, String> {
+        if base < 2 {
+            return Err(format!(
+                "the base of a concatenation should be at least 2, got {}",
+                base
+            ));
+        }
+        Ok(Self { base })
+    }
 
-        combinations.iter().any(|num| *num == self.result)
+    /// Power of the base shifting the value before past the digits of `number`, `None` when out
+    /// of range.
+    fn shift(
+        &self,
+        number: u64,
+    ) -> Option<u64> {
+        let mut shift = self.base;
+        while shift <= number {
+            shift = shift.checked_mul(self.base)?;
+        }
+        Some(shift)
     }
 }
 
-#[derive(Clone, Debug)]
-enum Operator {
-    Add,
-    Multiply,
-    Concat,
+impl Display for Concat {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self.base {
+            10 => write!(f, "||"),
+            base => write!(f, "||{}", base),
+        }
+    }
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Calibration>> {
-    all_consuming(separated_list1(
-        line_ending,
-        map(
-            separated_pair(
-                nom::character::complete::i64,
-                tuple((char(':'), space1)),
-                separated_list1(space1, nom::character::complete::i64),
-            ),
-            |(result, numbers)| Calibration { result, numbers },
-        ),
-    ))(input)
+impl Operator for Concat {
+    fn apply(
+        &self,
+        left: u64,
+        number: u64,
+    ) -> Option<u64> {
+        match self.shift(number) {
+            Some(shift) => left.checked_mul(shift)?.checked_add(number),
+            None => (left == 0).then_some(number),
+        }
+    }
+
+    fn undo(
+        &self,
+        result: u64,
+        number: u64,
+    ) -> Preimage {
+        match self.shift(number) {
+            Some(shift) => (result % shift == number).then(|| result / shift).into(),
+            None => (result == number).then_some(0).into(),
+        }
+    }
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let (_, calibrations) = parse(data).finish().unwrap();
+/// `-`, only when the value before is at least the number.
+#[derive(Copy, Clone, Debug)]
+pub struct Subtract;
 
-    let res: i64 = calibrations
-        .iter()
-        .filter_map(|calibration| {
-            if calibration.has_matching_combination(vec![Operator::Add, Operator::Multiply]) {
-                Some(calibration.result)
-            } else {
-                None
+impl Display for Subtract {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "-")
+    }
+}
+
+impl Operator for Subtract {
+    fn apply(
+        &self,
+        left: u64,
+        number: u64,
+    ) -> Option<u64> {
+        left.checked_sub(number)
+    }
+
+    fn undo(
+        &self,
+        result: u64,
+        number: u64,
+    ) -> Preimage {
+        result.checked_add(number).into()
+    }
+}
+
+/// `^`, bitwise exclusive or.
+#[derive(Copy, Clone, Debug)]
+pub struct Xor;
+
+impl Display for Xor {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "^")
+    }
+}
+
+impl Operator for Xor {
+    fn apply(
+        &self,
+        left: u64,
+        number: u64,
+    ) -> Option<u64> {
+        Some(left ^ number)
+    }
+
+    fn undo(
+        &self,
+        result: u64,
+        number: u64,
+    ) -> Preimage {
+        Preimage::Exact(result ^ number)
+    }
+}
+
+/// `/`, integer division rounding down.
+#[derive(Copy, Clone, Debug)]
+pub struct Divide;
+
+impl Display for Divide {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "/")
+    }
+}
+
+impl Operator for Divide {
+    fn apply(
+        &self,
+        left: u64,
+        number: u64,
+    ) -> Option<u64> {
+        left.checked_div(number)
+    }
+
+    fn undo(
+        &self,
+        result: u64,
+        number: u64,
+    ) -> Preimage {
+        match result.checked_mul(number) {
+            Some(low) if number > 0 => Preimage::Within(low..=low.saturating_add(number - 1)),
+            _ => Preimage::Nothing,
+        }
+    }
+}
+
+/// Operators an equation can use, in the order they are tried.
+#[derive(Debug)]
+pub struct Operators {
+    operators: Vec<Box<dyn Operator>>,
+}
+
+impl Operators {
+    pub fn new(operators: Vec<Box<dyn Operator>>) -> Self {
+        Self { operators }
+    }
+
+    /// Operators written with their symbols, separated by spaces or commas: `+`, `*`, `||`,
+    /// `||<base>`, `-`, `^` and `/`. Fails unless there is at least one.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        spec.split([' ', ','])
+            .filter(|symbol| !symbol.is_empty())
+            .map(|symbol| {
+                let operator: Box<dyn Operator> = match symbol {
+                    "+" => Box::new(Add),
+                    "*" => Box::new(Multiply),
+                    "-" => Box::new(Subtract),
+                    "^" => Box::new(Xor),
+                    "/" => Box::new(Divide),
+                    "||" => Box::new(Concat::DECIMAL),
+                    _ => {
+                        let base = symbol
+                            .strip_prefix("||")
+                            .and_then(|base| base.parse().ok())
+                            .ok_or_else(|| format!("unknown operator '{}'", symbol))?;
+                        Box::new(Concat::new(base)?)
+                    }
+                };
+                Ok(operator)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|operators| {
+                if operators.is_empty() {
+                    return Err("no operator given".to_string());
+                }
+                Ok(Self::new(operators))
+            })
+    }
+
+    /// Operators of the puzzle, only adding and multiplying in part 1.
+    pub fn standard(part: Part) -> Self {
+        match part {
+            Part::First => Self::new(vec![Box::new(Add), Box::new(Multiply)]),
+            Part::Second => {
+                Self::new(vec![
+                    Box::new(Add),
+                    Box::new(Multiply),
+                    Box::new(Concat::DECIMAL),
+                ])
             }
-        })
-        .sum();
+        }
+    }
+
+    /// Calibrations becoming solvable as each operator is added to the ones before it.
+    pub fn progression<'a>(
+        &'a self,
+        calibrations: &[Calibration],
+    ) -> Vec<Step<'a>> {
+        let mut solved = vec![false; calibrations.len()];
+        let (mut solvable, mut total) = (0, 0);
+
+        (1..=self.operators.len())
+            .map(|len| {
+                let operators = &self.operators[..len];
+                let mut equations = Vec::new();
+                for (calibration, solved) in calibrations.iter().zip(solved.iter_mut()) {
+                    // Adding an operator never makes an equation invalid
+                    if *solved {
+                        continue;
+                    }
+                    if let Some(equation) = calibration.equation(operators) {
+                        *solved = true;
+                        solvable += 1;
+                        total += calibration.result;
+                        equations.push(equation);
+                    }
+                }
+
+                Step {
+                    operator: operators[len - 1].as_ref(),
+                    equations,
+                    solvable,
+                    total,
+                }
+            })
+            .collect()
+    }
+}
 
-    println!("{}: {}", name, res);
+/// Calibrations solved by adding an operator, and all those solvable with it.
+#[derive(Clone, Debug)]
+pub struct Step<'a> {
+    pub operator: &'a dyn Operator,
+    /// Equations of the calibrations which were not solvable without the operator.
+    pub equations: Vec<Equation<'a>>,
+    pub solvable: usize,
+    pub total: u64,
 }
 
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let (_, calibrations) = parse(data).finish().unwrap();
+/// Numbers of a calibration combined left to right by `operators`, one between each pair.
+#[derive(Clone, Debug)]
+pub struct Equation<'a> {
+    pub result: u64,
+    pub numbers: Vec<u64>,
+    pub operators: Vec<&'a dyn Operator>,
+}
 
-    let res: i64 = calibrations
-        .iter()
-        .filter_map(|calibration| {
-            if calibration.has_matching_combination(vec![
-                Operator::Add,
-                Operator::Multiply,
-                Operator::Concat,
-            ]) {
-                Some(calibration.result)
-            } else {
-                None
+impl Display for Equation<'_> {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{} =", self.result)?;
+        for (idx, number) in self.numbers.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " {}", self.operators[idx - 1])?;
             }
+            write!(f, " {}", number)?;
+        }
+        Ok(())
+    }
+}
+
+type Trace<'a> = [Option<&'a dyn Operator>];
+
+impl Calibration {
+    pub fn result(&self) -> u64 {
+        self.result
+    }
+
+    /// First equation found with `operators`, searching back from the result: each operator
+    /// undoes the last number, and is only tried when the value before it can exist.
+    pub fn solve<'a>(
+        &self,
+        operators: &'a Operators,
+    ) -> Option<Equation<'a>> {
+        self.equation(&operators.operators)
+    }
+
+    fn equation<'a>(
+        &self,
+        operators: &'a [Box<dyn Operator>],
+    ) -> Option<Equation<'a>> {
+        let operators: Vec<&dyn Operator> = operators.iter().map(AsRef::as_ref).collect();
+        let (first, rest) = self.numbers.split_first()?;
+
+        let mut trace = vec![None; rest.len()];
+        if !search(self.result, *first, rest, &operators, &mut trace) {
+            return None;
+        }
+
+        Some(Equation {
+            result: self.result,
+            numbers: self.numbers.clone(),
+            operators: trace.into_iter().collect::<Option<_>>()?,
         })
-        .sum();
+    }
+}
+
+/// Whether `first` and `rest` can be combined into `target`, undoing the last number first and
+/// recording the operator before each number of `rest` in `trace`.
+fn search<'a>(
+    target: u64,
+    first: u64,
+    rest: &[u64],
+    operators: &[&'a dyn Operator],
+    trace: &mut Trace<'a>,
+) -> bool {
+    let (Some((&last, rest)), Some((slot, trace))) = (rest.split_last(), trace.split_last_mut())
+    else {
+        return target == first;
+    };
+
+    for &operator in operators {
+        *slot = Some(operator);
+        let found = match operator.undo(target, last) {
+            Preimage::Nothing => false,
+            Preimage::Exact(previous) => search(previous, first, rest, operators, trace),
+            // Several values could come before, so the numbers left are combined forward instead
+            Preimage::Within(range) => reach(&range, first, rest, operators, trace),
+        };
+        if found {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether `value` and `rest` can be combined into a value within `range`, trying every operator
+/// between each pair and recording them in `trace`.
+fn reach<'a>(
+    range: &RangeInclusive<u64>,
+    value: u64,
+    rest: &[u64],
+    operators: &[&'a dyn Operator],
+    trace: &mut Trace<'a>,
+) -> bool {
+    let (Some((&next, rest)), Some((slot, trace))) = (rest.split_first(), trace.split_first_mut())
+    else {
+        return range.contains(&value);
+    };
 
-    println!("{}: {}", name, res);
+    for &operator in operators {
+        if let Some(value) = operator.apply(value, next) {
+            *slot = Some(operator);
+            if reach(range, value, rest, operators, trace) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Sum of the results of the calibrations having an equation with `operators`.
+fn total(
+    calibrations: &[Calibration],
+    operators: &Operators,
+) -> u64 {
+    calibrations
+        .iter()
+        .filter(|calibration| calibration.solve(operators).is_some())
+        .map(|calibration| calibration.result)
+        .sum()
+}
+
+fn parse(input: &str) -> IResult<&str, Vec<Calibration>> {
+    all_consuming(terminated(
+        separated_list1(
+            line_ending,
+            map(
+                separated_pair(
+                    nom::character::complete::u64,
+                    tuple((char(':'), space1)),
+                    separated_list1(space1, nom::character::complete::u64),
+                ),
+                |(result, numbers)| Calibration { result, numbers },
+            ),
+        ),
+        opt(line_ending),
+    ))(input)
 }
 
-pub fn run() {
-    first("First Example", include_str!("data/day7/ex1"));
-    first("First", include_str!("data/day7/input"));
-    second("Second Example", include_str!("data/day7/ex1"));
-    second("Second", include_str!("data/day7/input"));
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Calibration>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(total(input, &Operators::standard(Part::First)).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(total(input, &Operators::standard(Part::Second)).into())
+    }
 }
@@ -1,194 +1,407 @@
-#[derive(Clone, Debug)]
-struct File {
-    id: i32,
-    size: i32,
+use crate::{
+    answer::Answer,
+    csv,
+    error::{
+        ParseError,
+        SolveError,
+    },
+    solution::Solution,
+};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::Display,
+    str::FromStr,
+};
+
+/// Contiguous blocks of a file, or of a part of it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Extent {
+    pub id: usize,
+    pub offset: usize,
+    pub size: usize,
 }
 
-#[derive(Clone, Debug)]
-struct Empty {
-    size: i32,
+/// Contiguous free blocks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub offset: usize,
+    pub size: usize,
 }
 
-#[derive(Clone, Debug)]
-enum Block {
-    File(File),
-    Empty(Empty),
+/// Where whole files are moved when compacting the disk.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Leftmost free span large enough, as in the puzzle.
+    FirstFit,
+    /// Smallest free span large enough, the leftmost one among equals.
+    BestFit,
+    /// Largest free span, the leftmost one among equals.
+    WorstFit,
+    /// Every file packed after the previous one, closing all the gaps.
+    Defragment,
 }
 
-#[derive(Clone, Debug)]
-enum Bit {
-    File(i32),
-    Empty,
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [
+        Strategy::FirstFit,
+        Strategy::BestFit,
+        Strategy::WorstFit,
+        Strategy::Defragment,
+    ];
 }
 
-fn parse(input: &str) -> Vec<Block> {
-    let mut blocks = Vec::new();
+impl FromStr for Strategy {
+    type Err = String;
 
-    let mut file_id = 0;
-    for (idx, c) in input.chars().enumerate() {
-        let size = c.to_digit(10).unwrap() as i32;
-        if idx % 2 == 0 {
-            blocks.push(Block::File(File { id: file_id, size }));
-            file_id += 1;
-        } else {
-            blocks.push(Block::Empty(Empty { size }));
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Strategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "invalid strategy '{}', expected first-fit, best-fit, worst-fit or defragment",
+                    s
+                )
+            })
+    }
+}
+
+impl Display for Strategy {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Strategy::FirstFit => write!(f, "first-fit"),
+            Strategy::BestFit => write!(f, "best-fit"),
+            Strategy::WorstFit => write!(f, "worst-fit"),
+            Strategy::Defragment => write!(f, "defragment"),
         }
     }
+}
 
-    blocks
+/// Blocks of a file moved from one offset to another.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub id: usize,
+    pub from: usize,
+    pub to: usize,
+    pub size: usize,
 }
 
-fn first(
-    name: &str,
-    data: &str,
-) {
-    let blocks = parse(data);
+/// Files after compacting the disk, and the moves that put them there.
+#[derive(Clone, Debug, Default)]
+pub struct Compaction {
+    pub extents: Vec<Extent>,
+    pub moves: Vec<Move>,
+}
 
-    let total_size = blocks
-        .iter()
-        .map(|block| {
-            match block {
-                Block::File(File { size, .. }) => *size as usize,
-                Block::Empty(Empty { size }) => *size as usize,
-            }
-        })
-        .sum::<usize>();
-
-    let mut disk = Vec::with_capacity(total_size);
-    for block in blocks {
-        match block {
-            Block::File(File { size, id }) => {
-                for _ in 0..size {
-                    disk.push(Bit::File(id));
-                }
-            }
-            Block::Empty(Empty { size }) => {
-                for _ in 0..size {
-                    disk.push(Bit::Empty);
-                }
-            }
-        }
+impl Compaction {
+    /// File id, offsets and size of each move, as CSV.
+    pub fn moves_csv(&self) -> String {
+        csv::table(
+            ["id", "from", "to", "size"],
+            self.moves.iter().map(|item| {
+                [item.id, item.from, item.to, item.size].map(|field| field.to_string())
+            }),
+        )
     }
 
-    let mut left_index = 0;
-    let mut right_index = disk.len() - 1;
+    /// Number of blocks moved.
+    pub fn moved(&self) -> usize {
+        self.moves.iter().map(|item| item.size).sum()
+    }
 
-    while left_index < right_index {
-        let left = &disk[left_index];
+    /// Free spans left between files, and the number of blocks they hold.
+    pub fn fragmentation(&self) -> (usize, usize) {
+        let mut extents: Vec<Extent> = self
+            .extents
+            .iter()
+            .filter(|extent| extent.size > 0)
+            .copied()
+            .collect();
+        extents.sort_unstable_by_key(|extent| extent.offset);
 
-        match left {
-            Bit::File(_) => {
-                left_index += 1;
-                continue;
-            }
-            Bit::Empty => {}
-        }
+        extents
+            .windows(2)
+            .map(|pair| pair[1].offset - (pair[0].offset + pair[0].size))
+            .filter(|gap| *gap > 0)
+            .fold((0, 0), |(spans, blocks), gap| (spans + 1, blocks + gap))
+    }
+}
 
-        let right = &disk[right_index];
-        let right_file_id = match right {
-            Bit::Empty => {
-                right_index -= 1;
-                continue;
-            }
-            Bit::File(file_id) => *file_id,
+/// Character of a block in a layout: the file id when it has a single digit, a letter up to 35,
+/// `#` beyond and `.` for free blocks.
+fn block_char(block: Option<usize>) -> char {
+    match block {
+        Some(id) => char::from_digit(id as u32, 36).unwrap_or('#'),
+        None => '.',
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Disk {
+    /// Files, in increasing id and offset.
+    files: Vec<Extent>,
+    /// Free spans, in increasing offset.
+    free: Vec<Span>,
+}
+
+/// Free spans bucketed by size, each bucket giving the leftmost span first.
+struct FreeSpans {
+    buckets: Vec<BinaryHeap<Reverse<usize>>>,
+}
+
+impl FreeSpans {
+    fn new(spans: &[Span]) -> Self {
+        // Spans merged across empty files can be larger than a digit, and spans only shrink.
+        let largest = spans.iter().map(|span| span.size).max().unwrap_or(0);
+        let mut free = Self {
+            buckets: vec![BinaryHeap::new(); largest + 1],
         };
+        for span in spans {
+            free.push(*span);
+        }
+        free
+    }
 
-        disk[left_index] = Bit::File(right_file_id);
-        disk[right_index] = Bit::Empty;
+    fn push(
+        &mut self,
+        span: Span,
+    ) {
+        if span.size > 0 {
+            self.buckets[span.size].push(Reverse(span.offset));
+        }
+    }
+
+    /// Removes the span of at least `size` blocks starting before `limit` that `strategy` picks.
+    fn pop(
+        &mut self,
+        strategy: Strategy,
+        size: usize,
+        limit: usize,
+    ) -> Option<Span> {
+        // The leftmost span of each size large enough, a bucket being skipped when even its
+        // leftmost span is past the limit.
+        let mut candidates = self
+            .buckets
+            .iter()
+            .enumerate()
+            .skip(size)
+            .filter_map(|(span_size, bucket)| bucket.peek().map(|offset| (span_size, offset.0)))
+            .filter(|(_, offset)| *offset < limit);
+
+        let (span_size, offset) = match strategy {
+            Strategy::FirstFit => candidates.min_by_key(|(_, offset)| *offset),
+            Strategy::BestFit => candidates.next(),
+            Strategy::WorstFit => candidates.next_back(),
+            Strategy::Defragment => unreachable!("defragmenting does not look for free spans"),
+        }?;
+
+        self.buckets[span_size].pop();
+        Some(Span {
+            offset,
+            size: span_size,
+        })
     }
+}
 
-    let result = disk
+/// Sum of the products of each block position and the id of the file it holds.
+pub fn checksum(extents: &[Extent]) -> u64 {
+    extents
         .iter()
-        .enumerate()
-        .map(|(idx, value)| {
-            (idx as i64)
-                * match value {
-                    Bit::File(file_id) => *file_id as i64,
-                    Bit::Empty => 0,
-                }
+        .map(|extent| {
+            let (id, offset, size) = (extent.id as u64, extent.offset as u64, extent.size as u64);
+            // Sum of the positions from offset to offset + size - 1
+            id * (size * offset + size * size.saturating_sub(1) / 2)
         })
-        .sum::<i64>();
-
-    println!("{}: {}", name, result);
-}
-
-fn second(
-    name: &str,
-    data: &str,
-) {
-    let mut blocks = parse(data);
-
-    let mut block_idx = blocks.len() - 1;
-    while block_idx > 0 {
-        let block = blocks[block_idx].clone();
-
-        match block {
-            Block::File(File { size, id }) => {
-                // Find the first block that has enough empty space
-                let empty_idx = blocks.iter().enumerate().find_map(|(idx, block)| {
-                    if idx > block_idx {
-                        None
-                    } else {
-                        match block {
-                            Block::Empty(empty) => {
-                                if empty.size >= size {
-                                    Some((idx, empty.clone()))
-                                } else {
-                                    None
-                                }
-                            }
-                            _ => None,
-                        }
-                    }
+        .sum()
+}
+
+impl Disk {
+    /// Number of blocks of the disk.
+    pub fn len(&self) -> usize {
+        let files = self.files.last().map(|file| file.offset + file.size);
+        let free = self.free.last().map(|span| span.offset + span.size);
+        files.max(free).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn files(&self) -> &[Extent] {
+        &self.files
+    }
+
+    /// The disk, in the style of the puzzle statement (`00...111...2...`), before and after each
+    /// of `moves`.
+    pub fn layouts<'a>(
+        &self,
+        moves: &'a [Move],
+    ) -> impl Iterator<Item = String> + 'a {
+        let mut blocks = vec![None; self.len()];
+        for file in self.files.iter() {
+            blocks[file.offset..file.offset + file.size].fill(Some(file.id));
+        }
+        let render = |blocks: &[Option<usize>]| blocks.iter().copied().map(block_char).collect();
+
+        let initial = render(&blocks);
+        std::iter::once(initial).chain(moves.iter().map(move |item| {
+            blocks[item.from..item.from + item.size].fill(None);
+            blocks[item.to..item.to + item.size].fill(Some(item.id));
+            render(&blocks)
+        }))
+    }
+
+    /// Moves blocks one at a time from the end of the disk to the leftmost free block, splitting
+    /// files.
+    pub fn compact_blocks(&self) -> Compaction {
+        let mut compaction = Compaction::default();
+        let mut files = self.files.clone();
+
+        'spans: for span in self.free.iter() {
+            let (mut offset, mut size) = (span.offset, span.size);
+
+            while size > 0 {
+                let Some(last) = files.last_mut() else {
+                    break 'spans;
+                };
+                if last.offset < offset {
+                    break 'spans;
+                }
+
+                let moved = size.min(last.size);
+                compaction.extents.push(Extent {
+                    id: last.id,
+                    offset,
+                    size: moved,
                 });
+                if moved > 0 {
+                    compaction.moves.push(Move {
+                        id: last.id,
+                        from: last.offset + last.size - moved,
+                        to: offset,
+                        size: moved,
+                    });
+                }
+                offset += moved;
+                size -= moved;
 
-                if let Some((idx, empty)) = empty_idx {
-                    blocks[block_idx] = Block::Empty(Empty { size });
-                    if empty.size == size {
-                        blocks[idx] = Block::File(File { size, id });
-                        block_idx -= 1;
-                    } else {
-                        blocks[idx] = Block::File(File { size, id });
-                        blocks.insert(
-                            idx + 1,
-                            Block::Empty(Empty {
-                                size: empty.size - size,
-                            }),
-                        );
-                    }
-                } else {
-                    block_idx -= 1;
+                last.size -= moved;
+                if last.size == 0 {
+                    files.pop();
                 }
             }
-            Block::Empty(_) => {
-                block_idx -= 1;
-                continue;
+        }
+
+        compaction.extents.extend(files);
+        compaction
+    }
+
+    /// Moves whole files, from the highest id down, to the free span on their left that `strategy`
+    /// picks, or packs every file after the previous one when defragmenting.
+    pub fn compact_files(
+        &self,
+        strategy: Strategy,
+    ) -> Compaction {
+        if strategy == Strategy::Defragment {
+            return self.defragment();
+        }
+
+        let mut free = FreeSpans::new(&self.free);
+        let mut extents = self.files.clone();
+        let mut moves = Vec::new();
+
+        for file in extents.iter_mut().rev().filter(|file| file.size > 0) {
+            if let Some(span) = free.pop(strategy, file.size, file.offset) {
+                moves.push(Move {
+                    id: file.id,
+                    from: file.offset,
+                    to: span.offset,
+                    size: file.size,
+                });
+                file.offset = span.offset;
+                free.push(Span {
+                    offset: span.offset + file.size,
+                    size: span.size - file.size,
+                });
             }
         }
+
+        Compaction { extents, moves }
     }
 
-    let result = blocks
-        .iter()
-        .flat_map(|block| {
-            match block {
-                Block::File(File { id, size }) => {
-                    vec![*id; *size as usize]
-                }
-                Block::Empty(Empty { size }) => {
-                    vec![0; *size as usize]
-                }
+    /// Packs the files in id order from the start of the disk, leaving all the free blocks at the
+    /// end.
+    fn defragment(&self) -> Compaction {
+        let mut compaction = Compaction::default();
+
+        let mut offset = 0;
+        for file in self.files.iter() {
+            if file.offset != offset && file.size > 0 {
+                compaction.moves.push(Move {
+                    id: file.id,
+                    from: file.offset,
+                    to: offset,
+                    size: file.size,
+                });
             }
-        })
+            compaction.extents.push(Extent { offset, ..*file });
+            offset += file.size;
+        }
+
+        compaction
+    }
+}
+
+fn parse(input: &str) -> Result<Disk, ParseError> {
+    let mut files = Vec::new();
+    let mut free: Vec<Span> = Vec::new();
+
+    let mut offset = 0;
+    for (idx, (char_offset, c)) in input
+        .trim_end_matches(['\r', '\n'])
+        .char_indices()
         .enumerate()
-        .map(|(idx, value)| (idx as i64) * (value as i64))
-        .sum::<i64>();
+    {
+        let size =
+            c.to_digit(10)
+                .ok_or_else(|| ParseError::at(input, char_offset, "a digit"))? as usize;
+        if idx % 2 == 0 {
+            files.push(Extent {
+                id: files.len(),
+                offset,
+                size,
+            });
+        } else {
+            // An empty file does not separate the free spans around it.
+            match free.last_mut() {
+                Some(last) if last.offset + last.size == offset => last.size += size,
+                _ => free.push(Span { offset, size }),
+            }
+        }
+        offset += size;
+    }
 
-    println!("{}: {}", name, result);
+    Ok(Disk { files, free })
 }
 
-pub fn run() {
-    first("First Example", include_str!("data/day9/ex1"));
-    first("First", include_str!("data/day9/input"));
-    second("Second Example", include_str!("data/day9/ex1"));
-    second("Second", include_str!("data/day9/input"));
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Disk;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        parse(data)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(checksum(&input.compact_blocks().extents).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError> {
+        Ok(checksum(&input.compact_files(Strategy::FirstFit).extents).into())
+    }
 }
@@ -0,0 +1,187 @@
+use crate::{
+    input::InputError,
+    solution::Part,
+};
+use nom::{
+    error::ErrorKind,
+    Finish,
+    IResult,
+};
+use std::fmt::Display;
+
+/// Position in the puzzle data where parsing failed, and what was expected there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, counted in characters.
+    pub column: usize,
+    pub expected: String,
+    pub found: Option<char>,
+}
+
+impl ParseError {
+    /// Builds the error for a failure at byte `offset` of `data`.
+    pub fn at(
+        data: &str,
+        offset: usize,
+        expected: impl Into<String>,
+    ) -> Self {
+        let before = &data[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let column = before[line_start..].chars().count() + 1;
+
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+            found: data[offset..].chars().next(),
+        }
+    }
+
+    /// Builds the error for a failure on `remaining`, a suffix of `data`.
+    pub fn remaining(
+        data: &str,
+        remaining: &str,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self::at(data, data.len() - remaining.len(), expected)
+    }
+
+    fn expected(kind: ErrorKind) -> String {
+        match kind {
+            ErrorKind::Eof => "end of input".to_string(),
+            ErrorKind::Digit => "a number".to_string(),
+            ErrorKind::CrLf => "a line ending".to_string(),
+            ErrorKind::Space => "a space".to_string(),
+            ErrorKind::Char | ErrorKind::OneOf | ErrorKind::NoneOf | ErrorKind::Alt => {
+                "a valid character".to_string()
+            }
+            ErrorKind::Tag => "a keyword or separator".to_string(),
+            ErrorKind::IsNot => "some text".to_string(),
+            kind => kind.description().to_lowercase(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+
+        match self.found {
+            Some(found) => write!(f, "found {:?}", found),
+            None => write!(f, "found end of input"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+}
+
+/// Unwraps the result of a nom parser run over the whole of `data`.
+pub fn finish<'a, T>(
+    data: &'a str,
+    result: IResult<&'a str, T>,
+) -> Result<T, ParseError> {
+    result
+        .finish()
+        .map(|(_, value)| value)
+        .map_err(|err| ParseError::remaining(data, err.input, ParseError::expected(err.code)))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The data is well formed but does not describe a solvable puzzle.
+    Unsolvable(String),
+    /// The part has been scaffolded but not solved yet.
+    NotImplemented,
+    /// An intermediate value does not fit in the integer type used.
+    Overflow(String),
+}
+
+impl Display for SolveError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            SolveError::Unsolvable(reason) => write!(f, "puzzle cannot be solved: {}", reason),
+            SolveError::NotImplemented => write!(f, "not implemented yet"),
+            SolveError::Overflow(value) => write!(f, "{} overflows", value),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {
+}
+
+/// Any failure of the runner, with the day, file and part it happened on. Subcommands going beyond
+/// the two parts of a day fail with `Explore`, which has no part.
+#[derive(Debug)]
+pub enum Error {
+    Input(InputError),
+    Parse {
+        day: u8,
+        file: String,
+        source: ParseError,
+    },
+    Solve {
+        day: u8,
+        part: Part,
+        file: String,
+        source: SolveError,
+    },
+    Explore {
+        day: u8,
+        file: String,
+        source: SolveError,
+    },
+}
+
+impl Display for Error {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Error::Input(source) => write!(f, "{}", source),
+            Error::Parse { day, file, source } => {
+                write!(f, "day {}: could not parse '{}' at {}", day, file, source)
+            }
+            Error::Solve {
+                day,
+                part,
+                file,
+                source,
+            } => write!(f, "day {} part {} on '{}': {}", day, part, file, source),
+            Error::Explore { day, file, source } => {
+                write!(f, "day {} on '{}': {}", day, file, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Input(source) => Some(source),
+            Error::Parse { source, .. } => Some(source),
+            Error::Solve { source, .. } => Some(source),
+            Error::Explore { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(source: InputError) -> Self {
+        Error::Input(source)
+    }
+}
@@ -0,0 +1,430 @@
+use crate::error::ParseError;
+use nom::{
+    character::complete::line_ending,
+    combinator::{
+        opt,
+        verify,
+    },
+    multi::many1,
+    sequence::terminated,
+    IResult,
+    Parser,
+};
+use serde::Serialize;
+use std::{
+    fmt::Display,
+    ops::{
+        Add,
+        Index,
+        IndexMut,
+        Sub,
+    },
+    str::FromStr,
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Point {
+    pub row: i32,
+    pub col: i32,
+}
+
+impl Point {
+    pub const fn new(
+        row: i32,
+        col: i32,
+    ) -> Self {
+        Self { row, col }
+    }
+
+    pub fn step(
+        self,
+        direction: Direction,
+    ) -> Self {
+        self + direction.delta()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Point::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Point::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+impl Display for Point {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+impl FromStr for Point {
+    type Err = String;
+
+    /// Parses `row,col`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_once(',')
+            .and_then(|(row, col)| {
+                Some(Point::new(
+                    row.trim().parse().ok()?,
+                    col.trim().parse().ok()?,
+                ))
+            })
+            .ok_or_else(|| format!("invalid point '{}', expected <row>,<col>", s))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// Directions of an 8-connected grid.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+    /// Directions of a 4-connected grid.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(-1, 0),
+            Direction::Down => Point::new(1, 0),
+            Direction::Left => Point::new(0, -1),
+            Direction::Right => Point::new(0, 1),
+            Direction::UpLeft => Point::new(-1, -1),
+            Direction::UpRight => Point::new(-1, 1),
+            Direction::DownLeft => Point::new(1, -1),
+            Direction::DownRight => Point::new(1, 1),
+        }
+    }
+
+    /// Quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::UpLeft => Direction::UpRight,
+            Direction::UpRight => Direction::DownRight,
+            Direction::DownRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpLeft,
+        }
+    }
+}
+
+/// Rectangular grid stored row by row in a single `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(
+        num_rows: usize,
+        num_cols: usize,
+        cells: Vec<T>,
+    ) -> Self {
+        assert_eq!(
+            cells.len(),
+            num_rows * num_cols,
+            "a {}x{} grid needs {} cells",
+            num_rows,
+            num_cols,
+            num_rows * num_cols
+        );
+
+        Self {
+            cells,
+            num_rows,
+            num_cols,
+        }
+    }
+
+    pub fn filled(
+        num_rows: usize,
+        num_cols: usize,
+        value: T,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(num_rows, num_cols, vec![value; num_rows * num_cols])
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn contains(
+        &self,
+        point: Point,
+    ) -> bool {
+        point.row >= 0
+            && point.col >= 0
+            && (point.row as usize) < self.num_rows
+            && (point.col as usize) < self.num_cols
+    }
+
+    fn index_of(
+        &self,
+        point: Point,
+    ) -> Option<usize> {
+        if self.contains(point) {
+            Some(point.row as usize * self.num_cols + point.col as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(
+        &self,
+        point: Point,
+    ) -> Option<&T> {
+        self.index_of(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(
+        &mut self,
+        point: Point,
+    ) -> Option<&mut T> {
+        self.index_of(point).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let num_cols = self.num_cols;
+        (0..self.num_rows * num_cols)
+            .map(move |idx| Point::new((idx / num_cols) as i32, (idx % num_cols) as i32))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.num_cols.max(1))
+    }
+
+    /// Position of the first cell, row by row, matching `predicate`.
+    pub fn position(
+        &self,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Option<Point> {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(point, _)| point)
+    }
+
+    /// Neighbours of `point` that are within the grid, among the given `directions`.
+    pub fn neighbours_in<'a>(
+        &'a self,
+        point: Point,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .map(move |direction| point.step(*direction))
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// 4-connected neighbours of `point` that are within the grid.
+    pub fn neighbours(
+        &self,
+        point: Point,
+    ) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &Direction::ORTHOGONAL)
+    }
+
+    /// 8-connected neighbours of `point` that are within the grid.
+    pub fn neighbours8(
+        &self,
+        point: Point,
+    ) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &Direction::ALL)
+    }
+
+    /// Points from `start` (included) moving by `delta` until leaving the grid.
+    pub fn ray(
+        &self,
+        start: Point,
+        delta: Point,
+    ) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(start), move |point| Some(*point + delta))
+            .take_while(|point| self.contains(*point))
+    }
+
+    pub fn map<U>(
+        &self,
+        f: impl Fn(&T) -> U,
+    ) -> Grid<U> {
+        Grid::new(
+            self.num_rows,
+            self.num_cols,
+            self.cells.iter().map(f).collect(),
+        )
+    }
+
+    /// Renders the grid line by line, using `f` to draw each cell.
+    pub fn render(
+        &self,
+        f: impl Fn(Point, &T) -> char,
+    ) -> String {
+        let mut output = String::with_capacity(self.num_rows * (self.num_cols + 1));
+        for (point, value) in self.iter() {
+            output.push(f(point, value));
+            if point.col as usize == self.num_cols - 1 {
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(
+        &self,
+        point: Point,
+    ) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(
+        &mut self,
+        point: Point,
+    ) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", point))
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.render(|_, c| *c))
+    }
+}
+
+/// nom parser for a rectangular grid, one line per row, using `cell` for each cell. Parsing stops
+/// before the first row that does not have the same length as the first one.
+pub fn grid<'a, T, F>(mut cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>>
+where
+    F: Parser<&'a str, T, nom::error::Error<&'a str>>,
+{
+    move |input: &'a str| {
+        let (mut input, mut cells) = terminated(many1(|i| cell.parse(i)), opt(line_ending))(input)?;
+        let num_cols = cells.len();
+        let mut num_rows = 1;
+
+        while !input.is_empty() {
+            match terminated(
+                verify(many1(|i| cell.parse(i)), |row: &Vec<T>| {
+                    row.len() == num_cols
+                }),
+                opt(line_ending),
+            )(input)
+            {
+                Ok((rest, row)) => {
+                    cells.extend(row);
+                    num_rows += 1;
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((input, Grid::new(num_rows, num_cols, cells)))
+    }
+}
+
+/// Parses a rectangular grid of characters, converting each one with `cell`, which returns `None`
+/// for characters that are not allowed. `expected` describes the allowed characters.
+pub fn parse_chars<T>(
+    data: &str,
+    cell: impl Fn(char) -> Option<T>,
+    expected: &str,
+) -> Result<Grid<T>, ParseError> {
+    let mut cells = Vec::new();
+    let mut num_rows = 0;
+    let mut num_cols = 0;
+
+    for line in data.lines() {
+        let offset = line.as_ptr() as usize - data.as_ptr() as usize;
+        let mut row_len = 0;
+
+        for (idx, c) in line.char_indices() {
+            let value = cell(c).ok_or_else(|| ParseError::at(data, offset + idx, expected))?;
+            cells.push(value);
+            row_len += 1;
+        }
+
+        if num_rows == 0 {
+            num_cols = row_len;
+        } else if row_len != num_cols {
+            return Err(ParseError::at(
+                data,
+                offset,
+                format!("a row of {} cells", num_cols),
+            ));
+        }
+        num_rows += 1;
+    }
+
+    if num_rows == 0 || num_cols == 0 {
+        return Err(ParseError::at(data, 0, "at least one row"));
+    }
+
+    Ok(Grid::new(num_rows, num_cols, cells))
+}
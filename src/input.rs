@@ -0,0 +1,121 @@
+use std::{
+    fmt::Display,
+    io::{
+        self,
+        Read,
+    },
+    path::PathBuf,
+};
+
+/// Name of the personal puzzle input, the one that can be replaced by stdin.
+pub const PUZZLE_INPUT: &str = "input";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { path: PathBuf },
+    Io { path: PathBuf, source: io::Error },
+    Stdin(io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            InputError::Missing { path } => {
+                write!(
+                    f,
+                    "input file '{}' does not exist, download it or point --inputs / AOC_INPUTS to \
+                     the directory holding it",
+                    path.display()
+                )
+            }
+            InputError::Io { path, source } => {
+                write!(
+                    f,
+                    "could not read input file '{}': {}",
+                    path.display(),
+                    source
+                )
+            }
+            InputError::Stdin(source) => write!(f, "could not read input from stdin: {}", source),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Io { source, .. } => Some(source),
+            InputError::Stdin(source) => Some(source),
+        }
+    }
+}
+
+/// Resolves puzzle files as `<dir>/day<N>/<name>`, e.g. `data/day6/ex1`.
+#[derive(Clone, Debug)]
+pub struct Inputs {
+    dir: PathBuf,
+    stdin: Option<String>,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            stdin: None,
+        }
+    }
+
+    /// Reads stdin once and uses it in place of the personal puzzle input.
+    pub fn with_stdin(mut self) -> Result<Self, InputError> {
+        let mut data = String::new();
+        io::stdin()
+            .read_to_string(&mut data)
+            .map_err(InputError::Stdin)?;
+        self.stdin = Some(data);
+        Ok(self)
+    }
+
+    pub fn path(
+        &self,
+        day: u8,
+        name: &str,
+    ) -> PathBuf {
+        self.dir.join(format!("day{}", day)).join(name)
+    }
+
+    /// Where `load` reads `name` from, for error messages.
+    pub fn describe(
+        &self,
+        day: u8,
+        name: &str,
+    ) -> String {
+        if self.stdin.is_some() && name == PUZZLE_INPUT {
+            "<stdin>".to_string()
+        } else {
+            self.path(day, name).display().to_string()
+        }
+    }
+
+    pub fn load(
+        &self,
+        day: u8,
+        name: &str,
+    ) -> Result<String, InputError> {
+        if let Some(data) = self.stdin.as_ref().filter(|_| name == PUZZLE_INPUT) {
+            return Ok(data.clone());
+        }
+
+        let path = self.path(day, name);
+        std::fs::read_to_string(&path).map_err(|source| {
+            if source.kind() == io::ErrorKind::NotFound {
+                InputError::Missing { path }
+            } else {
+                InputError::Io { path, source }
+            }
+        })
+    }
+}
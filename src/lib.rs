@@ -0,0 +1,23 @@
+pub mod ansi;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod csv;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod registry;
+pub mod scaffold;
+pub mod solution;
@@ -1,16 +1,955 @@
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-fn main() {
-    day12::run();
+use aoc24::{
+    ansi,
+    answers::Answers,
+    bench::{
+        bench_day,
+        Report,
+    },
+    day10::{
+        Day10,
+        TrailRules,
+    },
+    day11::{
+        statistics,
+        Day11,
+        Rules,
+    },
+    day12::Day12,
+    day6::{
+        loops_csv,
+        Day6,
+        Guard,
+        Outcome,
+        Puzzle,
+    },
+    day7::{
+        Day7,
+        Operators,
+    },
+    day9::{
+        self,
+        Compaction,
+        Day9,
+        Strategy,
+    },
+    error::Error,
+    grid::Point,
+    input::{
+        Inputs,
+        PUZZLE_INPUT,
+    },
+    registry::{
+        self,
+        Day,
+    },
+    scaffold::scaffold,
+    solution::{
+        Part,
+        Solution,
+    },
+};
+use clap::{
+    Parser,
+    Subcommand,
+    ValueEnum,
+};
+use num_bigint::BigUint;
+use std::{
+    collections::HashSet,
+    io,
+    path::PathBuf,
+    process::ExitCode,
+};
+
+#[derive(Parser, Debug)]
+#[command(about = "Advent of Code 2024 solutions")]
+struct Cli {
+    /// Directory holding the `day<N>/ex1`, `day<N>/input`, ... puzzle files
+    #[arg(long, global = true, env = "AOC_INPUTS", default_value = "data")]
+    inputs: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the solutions of one day, or of every day with `--all`
+    Run {
+        /// Day to run
+        #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+        day: Option<u8>,
+
+        /// Part to run (1 or 2), both parts are run when omitted
+        #[arg(long)]
+        part: Option<Part>,
+
+        /// Run every registered day
+        #[arg(long)]
+        all: bool,
+
+        /// Read the puzzle input from stdin instead of `day<N>/input`
+        #[arg(long, conflicts_with = "all")]
+        stdin: bool,
+
+        /// Expected answers to check the results against, `<inputs>/answers.toml` when present
+        #[arg(long, env = "AOC_ANSWERS")]
+        answers: Option<PathBuf>,
+    },
+    /// Time parsing and both parts of each day on the puzzle inputs (use a release build)
+    Bench {
+        /// Day to benchmark, every day when omitted
+        #[arg(long)]
+        day: Option<u8>,
+
+        /// Number of runs averaged for each step
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// Save the timings as a JSON baseline
+        #[arg(long)]
+        save: Option<PathBuf>,
+
+        /// Compare the timings against a JSON baseline saved with `--save`
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Fail when a step is slower than the baseline by more than this percentage
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Animate the day 6 guard patrol in the terminal
+    Animate {
+        /// Puzzle file to animate
+        #[arg(long, default_value = PUZZLE_INPUT)]
+        file: String,
+
+        /// Part to animate, part 2 adds an obstruction making the guard loop
+        #[arg(long, default_value = "1")]
+        part: Part,
+
+        /// Frames per second, as fast as possible when 0
+        #[arg(long, default_value_t = 10.0)]
+        fps: f64,
+
+        /// Extra obstruction as `<row>,<col>`, in part 2 the first one making the guard loop by
+        /// default
+        #[arg(long)]
+        obstruction: Option<Point>,
+    },
+    /// List the obstructions making the day 6 guard loop, with the loop each one causes
+    Loops {
+        /// Puzzle file to search
+        #[arg(long, default_value = PUZZLE_INPUT)]
+        file: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = LoopsFormat::Text)]
+        format: LoopsFormat,
+
+        /// Draw the map with the obstructions as `O` first
+        #[arg(long)]
+        render: bool,
+    },
+    /// Score and rate the day 10 trails under custom rules
+    Trails {
+        /// Puzzle file to search
+        #[arg(long, default_value = PUZZLE_INPUT)]
+        file: String,
+
+        /// Height trails start at
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        start: i32,
+
+        /// Height trails end at
+        #[arg(long, default_value_t = 9, allow_negative_numbers = true)]
+        end: i32,
+
+        /// Smallest height difference between two consecutive cells
+        #[arg(long, default_value_t = 1, allow_negative_numbers = true)]
+        min_step: i32,
+
+        /// Largest height difference between two consecutive cells
+        #[arg(long, default_value_t = 1, allow_negative_numbers = true)]
+        max_step: i32,
+
+        /// Allow diagonal moves
+        #[arg(long)]
+        diagonal: bool,
+    },
+    /// Count the day 11 stones after any number of blinks, with arbitrary precision
+    Stones {
+        /// Puzzle file with the initial stones
+        #[arg(long, default_value = PUZZLE_INPUT)]
+        file: String,
+
+        /// Number of blinks
+        #[arg(long, default_value_t = 75)]
+        blinks: usize,
+
+        /// Rules replacing the puzzle ones, e.g. "0 -> 1; even -> split; * -> *2024"
+        #[arg(long)]
+        rules: Option<String>,
+
+        /// Save the total, distinct and largest values of each blink as CSV
+        #[arg(long)]
+        csv: Option<PathBuf>,
+    },
+    /// Draw the day 12 zones with their fences, corners and measures
+    Zones {
+        /// Puzzle file to draw
+        #[arg(long, default_value = PUZZLE_INPUT)]
+        file: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ZonesFormat::Ansi)]
+        format: ZonesFormat,
+    },
+    /// Print an equation for each day 7 calibration, or say there is none
+    Equations {
+        /// Puzzle file with the calibrations
+        #[arg(long, default_value = PUZZLE_INPUT)]
+        file: String,
+
+        /// Part whose operators are used, part 2 adds concatenation
+        #[arg(long, default_value = "2")]
+        part: Part,
+
+        /// Operators replacing those of the part, in the order they are tried, among `+`, `*`,
+        /// `||`, `||<base>`, `-`, `^` and `/`, e.g. "+ * || -"
+        #[arg(long)]
+        operators: Option<String>,
+
+        /// Show the calibrations becoming solvable as each operator is added, instead of every
+        /// equation
+        #[arg(long)]
+        progression: bool,
+    },
+    /// Compact the day 9 disk, showing each move
+    Compact {
+        /// Puzzle file with the disk map
+        #[arg(long, default_value = PUZZLE_INPUT)]
+        file: String,
+
+        /// Part to follow, part 1 moves blocks and part 2 whole files
+        #[arg(long, default_value = "2")]
+        part: Part,
+
+        /// Where part 2 moves files: first-fit, best-fit, worst-fit or defragment
+        #[arg(long, default_value = "first-fit")]
+        strategy: Strategy,
+
+        /// Compare the checksum, fragmentation and blocks moved of every strategy
+        #[arg(long)]
+        compare: bool,
+
+        /// Print the disk after each move, for small disks
+        #[arg(long)]
+        layouts: bool,
+
+        /// Save the moves (file id, from and to offsets, size) as CSV
+        #[arg(long)]
+        csv: Option<PathBuf>,
+    },
+    /// Generate and register the skeleton of a new day
+    New {
+        /// Day to generate
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Root of the project
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum LoopsFormat {
+    Text,
+    Csv,
+    Json,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum ZonesFormat {
+    Ansi,
+    Svg,
+}
+
+fn run_day(
+    day: &Day,
+    part: Option<Part>,
+    inputs: &Inputs,
+    answers: &Answers,
+) -> bool {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let mut success = true;
+    for name in day.files(&parts) {
+        let parsed = inputs
+            .load(day.number, name)
+            .map_err(Error::from)
+            .and_then(|data| {
+                (day.parse)(&data).map_err(|source| {
+                    Error::Parse {
+                        day: day.number,
+                        file: inputs.describe(day.number, name),
+                        source,
+                    }
+                })
+            });
+
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                success = false;
+                continue;
+            }
+        };
+
+        for part in parts.iter().filter(|part| day.runs(**part, name)) {
+            match parsed.solve(*part) {
+                Ok(answer) => {
+                    let check = match answers.expected(day.number, *part, name) {
+                        Some(expected) if *expected == answer => " ✓".to_string(),
+                        Some(expected) => {
+                            success = false;
+                            format!(" ✗ (expected {})", expected)
+                        }
+                        None => String::new(),
+                    };
+                    println!(
+                        "Day {} - Part {} - {}: {}{}",
+                        day.number, part, name, answer, check
+                    )
+                }
+                Err(source) => {
+                    let err = Error::Solve {
+                        day: day.number,
+                        part: *part,
+                        file: inputs.describe(day.number, name),
+                        source,
+                    };
+                    eprintln!("Error: {}", err);
+                    success = false;
+                }
+            }
+        }
+    }
+
+    success
+}
+
+fn find_day(number: u8) -> Option<&'static Day> {
+    let day = registry::find(number);
+    if day.is_none() {
+        eprintln!("Day {} is not implemented", number);
+    }
+    day
+}
+
+/// Loads `path`, or `default` when it exists.
+fn load_answers(
+    path: Option<PathBuf>,
+    default: PathBuf,
+) -> Option<Answers> {
+    let path = match path {
+        Some(path) => path,
+        None if default.exists() => default,
+        None => return Some(Answers::default()),
+    };
+
+    match Answers::load(&path) {
+        Ok(answers) => Some(answers),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            None
+        }
+    }
+}
+
+fn run(
+    inputs: Inputs,
+    day: Option<u8>,
+    part: Option<Part>,
+    all: bool,
+    stdin: bool,
+    answers: Answers,
+) -> bool {
+    let inputs = if stdin {
+        match inputs.with_stdin() {
+            Ok(inputs) => inputs,
+            Err(err) => {
+                eprintln!("{}", err);
+                return false;
+            }
+        }
+    } else {
+        inputs
+    };
+
+    if all {
+        let mut success = true;
+        for day in registry::DAYS.iter() {
+            success &= run_day(day, part, &inputs, &answers);
+        }
+        success
+    } else if let Some(day) = day {
+        find_day(day).is_some_and(|day| run_day(day, part, &inputs, &answers))
+    } else {
+        true
+    }
+}
+
+fn bench(
+    inputs: Inputs,
+    day: Option<u8>,
+    iterations: u32,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+) -> bool {
+    let days = match day {
+        Some(day) => {
+            match find_day(day) {
+                Some(day) => vec![day],
+                None => return false,
+            }
+        }
+        None => registry::DAYS.iter().collect(),
+    };
+
+    let baseline = match baseline.map(|path| Report::load(&path).map_err(|err| (path, err))) {
+        Some(Ok(baseline)) => Some(baseline),
+        Some(Err((path, err))) => {
+            eprintln!(
+                "Error: could not load baseline '{}': {}",
+                path.display(),
+                err
+            );
+            return false;
+        }
+        None => None,
+    };
+
+    let mut success = true;
+    let mut report = Report::default();
+    for day in days {
+        let timing = inputs
+            .load(day.number, PUZZLE_INPUT)
+            .map_err(|err| err.to_string())
+            .and_then(|data| bench_day(day, &data, iterations).map_err(|err| err.to_string()));
+
+        match timing {
+            Ok(timing) => report.timings.push(timing),
+            Err(err) => {
+                eprintln!("Error: day {}: {}", day.number, err);
+                success = false;
+            }
+        }
+    }
+
+    print!("{}", report.table(baseline.as_ref()));
+
+    if let Some(baseline) = baseline {
+        let regressions = report.regressions(&baseline, threshold);
+        if !regressions.is_empty() {
+            eprintln!("Slower than the baseline by more than {}%:", threshold);
+            for regression in regressions {
+                eprintln!("  {}", regression);
+            }
+            success = false;
+        }
+    }
+
+    if let Some(path) = save {
+        if let Err(err) = report.save(&path) {
+            eprintln!(
+                "Error: could not save baseline '{}': {}",
+                path.display(),
+                err
+            );
+            success = false;
+        }
+    }
+
+    success
+}
+
+/// Loads and parses `file` of `day` with the parser of `S`.
+fn load<S: Solution>(
+    inputs: &Inputs,
+    day: u8,
+    file: &str,
+) -> Result<S::Input, Error> {
+    let data = inputs.load(day, file)?;
+    S::parse(&data).map_err(|source| {
+        Error::Parse {
+            day,
+            file: inputs.describe(day, file),
+            source,
+        }
+    })
+}
+
+/// Loads the day 6 puzzle in `file` and finds its guard.
+fn patrol(
+    inputs: &Inputs,
+    file: &str,
+    part: Part,
+) -> Result<(Puzzle, Guard), Error> {
+    let puzzle = load::<Day6>(inputs, 6, file)?;
+    let guard = puzzle.guard().map_err(|source| {
+        Error::Solve {
+            day: 6,
+            part,
+            file: inputs.describe(6, file),
+            source,
+        }
+    })?;
+
+    Ok((puzzle, guard))
+}
+
+fn animate(
+    inputs: Inputs,
+    file: String,
+    part: Part,
+    fps: f64,
+    obstruction: Option<Point>,
+) -> bool {
+    let (puzzle, guard) = match patrol(&inputs, &file, part) {
+        Ok(patrol) => patrol,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return false;
+        }
+    };
+
+    if let Some(Err(err)) = obstruction.map(|point| puzzle.check_obstruction(guard, point)) {
+        eprintln!("Error: invalid obstruction, {}", err);
+        return false;
+    }
+
+    let extra = match (part, obstruction) {
+        (_, Some(obstruction)) => Some(obstruction),
+        (Part::First, None) => None,
+        (Part::Second, None) => {
+            match puzzle.loop_obstructions(guard).first() {
+                Some(obstruction) => Some(*obstruction),
+                None => {
+                    eprintln!("No obstruction makes the guard walk in a loop");
+                    return false;
+                }
+            }
+        }
+    };
+
+    let (outcome, frames) = puzzle.animation(guard, extra);
+    if let Err(err) = ansi::animate(&mut io::stdout().lock(), frames, fps) {
+        eprintln!("Error: {}", err);
+        return false;
+    }
+
+    match (outcome, extra) {
+        (Outcome::Loop, Some(extra)) => {
+            println!("The guard walks in a loop with an obstruction at {}", extra)
+        }
+        (Outcome::Loop, None) => println!("The guard walks in a loop"),
+        (Outcome::OutOfBounds, _) => println!("The guard left the map"),
+    }
+    true
+}
+
+fn loops(
+    inputs: Inputs,
+    file: String,
+    format: LoopsFormat,
+    render: bool,
+) -> bool {
+    let (puzzle, guard) = match patrol(&inputs, &file, Part::Second) {
+        Ok(patrol) => patrol,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return false;
+        }
+    };
+
+    let loops = puzzle.loops(guard);
+    if render {
+        let obstructions: Vec<Point> = loops.iter().map(|item| item.obstruction).collect();
+        println!("{}", puzzle.render_with(&obstructions));
+    }
+
+    match format {
+        LoopsFormat::Text => {
+            for item in loops.iter() {
+                println!(
+                    "{}: loop of {} cells entered at {} facing {:?}",
+                    item.obstruction, item.length, item.entry.position, item.entry.direction
+                );
+            }
+            println!("{} obstructions make the guard loop", loops.len());
+        }
+        LoopsFormat::Csv => print!("{}", loops_csv(&loops)),
+        LoopsFormat::Json => {
+            match serde_json::to_string_pretty(&loops) {
+                Ok(json) => println!("{}", json),
+                Err(err) => {
+                    eprintln!("Error: {}", err);
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+fn trails(
+    inputs: Inputs,
+    file: String,
+    rules: Result<TrailRules, String>,
+) -> bool {
+    let rules = match rules {
+        Ok(rules) => rules,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return false;
+        }
+    };
+
+    let totals = load::<Day10>(&inputs, 10, &file).and_then(|topology| {
+        topology.totals(&rules).map_err(|source| {
+            Error::Explore {
+                day: 10,
+                file: inputs.describe(10, &file),
+                source,
+            }
+        })
+    });
+
+    match totals {
+        Ok((score, rating)) => {
+            println!("Score: {}, rating: {}", score, rating);
+            true
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            false
+        }
+    }
+}
+
+fn stones(
+    inputs: Inputs,
+    file: String,
+    blinks: usize,
+    rules: Option<String>,
+    csv: Option<PathBuf>,
+) -> bool {
+    let rules = match rules.map(|rules| Rules::parse(&rules)) {
+        Some(Ok(rules)) => rules,
+        Some(Err(err)) => {
+            eprintln!("Error: invalid rules at {}", err);
+            return false;
+        }
+        None => Rules::standard(),
+    };
+
+    let statistics = load::<Day11>(&inputs, 11, &file).and_then(|stones| {
+        statistics::<BigUint>(&stones, blinks, &rules).map_err(|source| {
+            Error::Explore {
+                day: 11,
+                file: inputs.describe(11, &file),
+                source,
+            }
+        })
+    });
+
+    let statistics = match statistics {
+        Ok(statistics) => statistics,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return false;
+        }
+    };
+
+    if let Some(last) = statistics.blinks.last() {
+        println!("{} stones after {} blinks", last.total, blinks);
+    }
+    if let Some(blink) = statistics.closed_at {
+        let seen = statistics.blinks[blink].seen;
+        println!(
+            "No new value after blink {}, the stones only take {} values",
+            blink - 1,
+            seen
+        );
+    }
+
+    if let Some(path) = csv {
+        if let Err(err) = std::fs::write(&path, statistics.csv()) {
+            eprintln!("Error: could not write '{}': {}", path.display(), err);
+            return false;
+        }
+    }
+    true
+}
+
+fn zones(
+    inputs: Inputs,
+    file: String,
+    format: ZonesFormat,
+) -> bool {
+    match load::<Day12>(&inputs, 12, &file) {
+        Ok(puzzle) => {
+            let regions = puzzle.regions();
+            match format {
+                ZonesFormat::Ansi => print!("{}", regions.ansi()),
+                ZonesFormat::Svg => print!("{}", regions.svg()),
+            }
+            true
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            false
+        }
+    }
+}
+
+fn equations(
+    inputs: Inputs,
+    file: String,
+    part: Part,
+    operators: Option<String>,
+    progression: bool,
+) -> bool {
+    let operators = match operators.map(|operators| Operators::parse(&operators)) {
+        Some(Ok(operators)) => operators,
+        Some(Err(err)) => {
+            eprintln!("Error: {}", err);
+            return false;
+        }
+        None => Operators::standard(part),
+    };
+
+    let calibrations = match load::<Day7>(&inputs, 7, &file) {
+        Ok(calibrations) => calibrations,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return false;
+        }
+    };
+
+    if progression {
+        for step in operators.progression(&calibrations) {
+            println!(
+                "{}: {} more solvable, {} of {} with a total of {}",
+                step.operator,
+                step.equations.len(),
+                step.solvable,
+                calibrations.len(),
+                step.total
+            );
+            for equation in step.equations {
+                println!("    {}", equation);
+            }
+        }
+        return true;
+    }
+
+    let mut total = 0;
+    for calibration in calibrations.iter() {
+        match calibration.solve(&operators) {
+            Some(equation) => {
+                total += calibration.result();
+                println!("{}", equation);
+            }
+            None => println!("{}: no equation", calibration.result()),
+        }
+    }
+    println!("Total calibration result: {}", total);
+    true
+}
+
+/// Largest disk whose layouts are printed, in blocks.
+const MAX_LAYOUT_BLOCKS: usize = 200;
+
+fn compact(
+    inputs: Inputs,
+    file: String,
+    part: Part,
+    strategy: Strategy,
+    layouts: bool,
+    csv: Option<PathBuf>,
+    compare: bool,
+) -> bool {
+    let disk = match load::<Day9>(&inputs, 9, &file) {
+        Ok(disk) => disk,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return false;
+        }
+    };
+    if layouts && disk.len() > MAX_LAYOUT_BLOCKS {
+        eprintln!(
+            "Error: the disk has {} blocks, layouts are only printed up to {}",
+            disk.len(),
+            MAX_LAYOUT_BLOCKS
+        );
+        return false;
+    }
+
+    if compare {
+        let blocks = ("blocks".to_string(), disk.compact_blocks());
+        let files = Strategy::ALL
+            .into_iter()
+            .map(|strategy| (strategy.to_string(), disk.compact_files(strategy)));
+        print_comparison(std::iter::once(blocks).chain(files));
+        return true;
+    }
+
+    let compaction = match part {
+        Part::First => disk.compact_blocks(),
+        Part::Second => disk.compact_files(strategy),
+    };
+
+    if layouts {
+        for layout in disk.layouts(&compaction.moves) {
+            println!("{}", layout);
+        }
+    }
+    let moved: HashSet<usize> = compaction.moves.iter().map(|item| item.id).collect();
+    println!(
+        "{} moves, {} of {} files stayed in place, checksum {}",
+        compaction.moves.len(),
+        disk.files().len() - moved.len(),
+        disk.files().len(),
+        day9::checksum(&compaction.extents)
+    );
+
+    if let Some(path) = csv {
+        if let Err(err) = std::fs::write(&path, compaction.moves_csv()) {
+            eprintln!("Error: could not write '{}': {}", path.display(), err);
+            return false;
+        }
+    }
+    true
+}
+
+/// One line per compaction with its checksum, the free spans and blocks left between files, and
+/// the blocks moved.
+fn print_comparison(compactions: impl Iterator<Item = (String, Compaction)>) {
+    println!(
+        "{:<12} {:>16} {:>6} {:>11} {:>8}",
+        "strategy", "checksum", "gaps", "gap blocks", "moved"
+    );
+    for (name, compaction) in compactions {
+        let (gaps, gap_blocks) = compaction.fragmentation();
+        println!(
+            "{:<12} {:>16} {:>6} {:>11} {:>8}",
+            name,
+            day9::checksum(&compaction.extents),
+            gaps,
+            gap_blocks,
+            compaction.moved()
+        );
+    }
+}
+
+fn new(
+    day: u8,
+    root: PathBuf,
+) -> bool {
+    match scaffold(&root, day) {
+        Ok(files) => {
+            for file in files {
+                println!("Wrote {}", file.display());
+            }
+            true
+        }
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            false
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let default_answers = cli.inputs.join("answers.toml");
+    let inputs = Inputs::new(cli.inputs);
+
+    let success = match cli.command {
+        Command::Run {
+            day,
+            part,
+            all,
+            stdin,
+            answers,
+        } => {
+            load_answers(answers, default_answers)
+                .is_some_and(|answers| run(inputs, day, part, all, stdin, answers))
+        }
+        Command::Bench {
+            day,
+            iterations,
+            save,
+            baseline,
+            threshold,
+        } => bench(inputs, day, iterations, save, baseline, threshold),
+        Command::Animate {
+            file,
+            part,
+            fps,
+            obstruction,
+        } => animate(inputs, file, part, fps, obstruction),
+        Command::Loops {
+            file,
+            format,
+            render,
+        } => loops(inputs, file, format, render),
+        Command::Trails {
+            file,
+            start,
+            end,
+            min_step,
+            max_step,
+            diagonal,
+        } => {
+            let rules = TrailRules::new(start, end, min_step..=max_step, diagonal);
+            trails(inputs, file, rules)
+        }
+        Command::Stones {
+            file,
+            blinks,
+            rules,
+            csv,
+        } => stones(inputs, file, blinks, rules, csv),
+        Command::Zones { file, format } => zones(inputs, file, format),
+        Command::Equations {
+            file,
+            part,
+            operators,
+            progression,
+        } => equations(inputs, file, part, operators, progression),
+        Command::Compact {
+            file,
+            part,
+            strategy,
+            layouts,
+            csv,
+            compare,
+        } => compact(inputs, file, part, strategy, layouts, csv, compare),
+        Command::New { day, root } => new(day, root),
+    };
+
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
@@ -0,0 +1,132 @@
+use crate::{
+    day1,
+    day10,
+    day11,
+    day12,
+    day2,
+    day3,
+    day4,
+    day5,
+    day6,
+    day7,
+    day8,
+    day9,
+    input::PUZZLE_INPUT,
+    solution::{
+        parser,
+        Parser,
+        Part,
+    },
+};
+
+pub struct Day {
+    pub number: u8,
+    pub parse: Parser,
+    /// Example files to run before the puzzle input, for each part.
+    pub examples: [&'static [&'static str]; 2],
+}
+
+impl Day {
+    pub fn examples(
+        &self,
+        part: Part,
+    ) -> &'static [&'static str] {
+        match part {
+            Part::First => self.examples[0],
+            Part::Second => self.examples[1],
+        }
+    }
+
+    /// Files to run for `parts`, in order, each listed once so that it is only parsed once.
+    pub fn files(
+        &self,
+        parts: &[Part],
+    ) -> Vec<&'static str> {
+        let mut files = Vec::new();
+        for part in parts {
+            for name in self.examples(*part) {
+                if !files.contains(name) {
+                    files.push(*name);
+                }
+            }
+        }
+        files.push(PUZZLE_INPUT);
+        files
+    }
+
+    /// Whether the file `name` is meant to be solved for `part`.
+    pub fn runs(
+        &self,
+        part: Part,
+        name: &str,
+    ) -> bool {
+        name == PUZZLE_INPUT || self.examples(part).contains(&name)
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        parse: parser::<day1::Day1>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 2,
+        parse: parser::<day2::Day2>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 3,
+        parse: parser::<day3::Day3>,
+        examples: [&["ex1"], &["ex2"]],
+    },
+    Day {
+        number: 4,
+        parse: parser::<day4::Day4>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 5,
+        parse: parser::<day5::Day5>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 6,
+        parse: parser::<day6::Day6>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 7,
+        parse: parser::<day7::Day7>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 8,
+        parse: parser::<day8::Day8>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 9,
+        parse: parser::<day9::Day9>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 10,
+        parse: parser::<day10::Day10>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 11,
+        parse: parser::<day11::Day11>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+    Day {
+        number: 12,
+        parse: parser::<day12::Day12>,
+        examples: [&["ex1"], &["ex1", "ex2"]],
+    },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,224 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+const TEMPLATE: &str = r#"use crate::{
+    answer::Answer,
+    error::{
+        finish,
+        ParseError,
+        SolveError,
+    },
+    solution::Solution,
+};
+use nom::{
+    bytes::complete::is_not,
+    character::complete::line_ending,
+    combinator::{
+        all_consuming,
+        map,
+        opt,
+    },
+    multi::many0,
+    sequence::terminated,
+    IResult,
+};
+
+fn parse(input: &str) -> IResult<&str, Vec<String>> {
+    all_consuming(many0(terminated(
+        map(is_not("\r\n"), str::to_string),
+        opt(line_ending),
+    )))(input)
+}
+
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    type Input = Vec<String>;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError> {
+        finish(data, parse(data))
+    }
+
+    fn part1(_input: &Self::Input) -> Result<Answer, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+
+    fn part2(_input: &Self::Input) -> Result<Answer, SolveError> {
+        Err(SolveError::NotImplemented)
+    }
+}
+"#;
+
+const REGISTRY_ENTRY: &str = r#"    Day {
+        number: {N},
+        parse: parser::<day{N}::Day{N}>,
+        examples: [&["ex1"], &["ex1"]],
+    },
+"#;
+
+const TEST_STUB: &str = r#"
+#[test]
+#[ignore = "fill in the example answers of day {N}"]
+fn day{N}() {
+    check({N}, Part::First, "ex1", 0);
+    check({N}, Part::Second, "ex1", 0);
+}
+"#;
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    AlreadyExists(PathBuf),
+    Unexpected { path: PathBuf, reason: String },
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl Display for ScaffoldError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            ScaffoldError::AlreadyExists(path) => {
+                write!(f, "'{}' already exists", path.display())
+            }
+            ScaffoldError::Unexpected { path, reason } => {
+                write!(f, "could not update '{}': {}", path.display(), reason)
+            }
+            ScaffoldError::Io { path, source } => {
+                write!(f, "could not write '{}': {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScaffoldError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path).map_err(|source| {
+        ScaffoldError::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
+
+fn write(
+    path: &Path,
+    data: &str,
+) -> Result<(), ScaffoldError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|source| {
+            ScaffoldError::Io {
+                path: parent.to_path_buf(),
+                source,
+            }
+        })?;
+    }
+
+    std::fs::write(path, data).map_err(|source| {
+        ScaffoldError::Io {
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
+
+/// Inserts `line` in the first block of consecutive lines starting with `prefix`, keeping the block
+/// sorted the way rustfmt sorts it.
+fn insert_sorted(
+    data: &str,
+    prefix: &str,
+    line: &str,
+) -> Option<String> {
+    let mut lines: Vec<&str> = data.lines().collect();
+    let start = lines.iter().position(|l| l.starts_with(prefix))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .take_while(|l| l.starts_with(prefix))
+            .count();
+
+    lines.insert(end, line);
+    lines[start..=end].sort_unstable_by_key(|l| l.trim_end_matches([';', ',']));
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// Inserts the registry entry of `day` at the end of the `DAYS` array.
+fn register(
+    registry: &str,
+    day: u8,
+) -> Option<String> {
+    let with_import = insert_sorted(registry, "    day", &format!("    day{},", day))?;
+
+    let days_start = with_import.find("pub const DAYS")?;
+    let days_end = days_start + with_import[days_start..].find("\n];")? + 1;
+
+    Some(format!(
+        "{}{}{}",
+        &with_import[..days_end],
+        REGISTRY_ENTRY.replace("{N}", &day.to_string()),
+        &with_import[days_end..]
+    ))
+}
+
+/// Generates the module, registry entry, test stub and data files of `day` in the project at
+/// `root`, returning the files created or updated.
+pub fn scaffold(
+    root: &Path,
+    day: u8,
+) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let module = root.join(format!("src/day{}.rs", day));
+    if module.exists() {
+        return Err(ScaffoldError::AlreadyExists(module));
+    }
+
+    let lib = root.join("src/lib.rs");
+    let lib_data = insert_sorted(&read(&lib)?, "pub mod ", &format!("pub mod day{};", day))
+        .ok_or_else(|| {
+            ScaffoldError::Unexpected {
+                path: lib.clone(),
+                reason: "no `pub mod` declarations".to_string(),
+            }
+        })?;
+
+    let registry = root.join("src/registry.rs");
+    let registry_data = register(&read(&registry)?, day).ok_or_else(|| {
+        ScaffoldError::Unexpected {
+            path: registry.clone(),
+            reason: "no day imports or `DAYS` array".to_string(),
+        }
+    })?;
+
+    let tests = root.join("tests/examples.rs");
+    let tests_data = read(&tests)? + &TEST_STUB.replace("{N}", &day.to_string());
+
+    write(&module, &TEMPLATE.replace("{N}", &day.to_string()))?;
+    write(&lib, &lib_data)?;
+    write(&registry, &registry_data)?;
+    write(&tests, &tests_data)?;
+
+    let mut files = vec![module, lib, registry, tests];
+    for name in ["ex1", "input"] {
+        let path = root.join(format!("data/day{}/{}", day, name));
+        if !path.exists() {
+            write(&path, "")?;
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
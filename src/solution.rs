@@ -0,0 +1,103 @@
+use crate::{
+    answer::Answer,
+    error::{
+        ParseError,
+        SolveError,
+    },
+};
+use std::{
+    fmt::Display,
+    marker::PhantomData,
+    str::FromStr,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    First,
+    Second,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::First, Part::Second];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::First),
+            "2" => Ok(Part::Second),
+            _ => Err(format!("invalid part '{}', expected 1 or 2", s)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Part::First => write!(f, "1"),
+            Part::Second => write!(f, "2"),
+        }
+    }
+}
+
+/// A day of the calendar: the puzzle data is parsed once into `Input`, which both parts then
+/// solve.
+pub trait Solution {
+    type Input;
+
+    fn parse(data: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Result<Answer, SolveError>;
+
+    fn part2(input: &Self::Input) -> Result<Answer, SolveError>;
+
+    fn solve(
+        input: &Self::Input,
+        part: Part,
+    ) -> Result<Answer, SolveError> {
+        match part {
+            Part::First => Self::part1(input),
+            Part::Second => Self::part2(input),
+        }
+    }
+}
+
+/// Parsed input of any [`Solution`], so that days can be stored side by side in the registry.
+pub trait Parsed {
+    fn solve(
+        &self,
+        part: Part,
+    ) -> Result<Answer, SolveError>;
+}
+
+struct ParsedInput<S: Solution> {
+    input: S::Input,
+    solution: PhantomData<S>,
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(
+        &self,
+        part: Part,
+    ) -> Result<Answer, SolveError> {
+        S::solve(&self.input, part)
+    }
+}
+
+pub type Parser = fn(&str) -> Result<Box<dyn Parsed>, ParseError>;
+
+pub fn parser<S>(data: &str) -> Result<Box<dyn Parsed>, ParseError>
+where
+    S: Solution + 'static,
+{
+    let input = S::parse(data)?;
+    Ok(Box::new(ParsedInput::<S> {
+        input,
+        solution: PhantomData,
+    }))
+}
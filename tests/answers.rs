@@ -0,0 +1,91 @@
+//! Parses answers files, see `aoc24::answers::Answers` for the format, and checks personal inputs
+//! against a local one. Run the latter with `cargo test -- --ignored`; `AOC_INPUTS` and
+//! `AOC_ANSWERS` override the default `data` and `data/answers.toml` locations.
+
+use aoc24::{
+    answer::Answer,
+    answers::{
+        Answers,
+        AnswersError,
+    },
+    input::Inputs,
+    registry,
+    solution::Part,
+};
+use std::path::PathBuf;
+
+#[test]
+fn parses_answers() {
+    let answers =
+        Answers::parse("[day12.part2]\nex1 = 1206\nex2 = 368\n\n[day13.part1]\ninput = \"ABC\"\n")
+            .unwrap();
+
+    assert_eq!(
+        answers.expected(12, Part::Second, "ex1"),
+        Some(&Answer::from(1206))
+    );
+    assert_eq!(
+        answers.expected(13, Part::First, "input"),
+        Some(&Answer::from("ABC"))
+    );
+    assert_eq!(answers.expected(12, Part::First, "ex1"), None);
+    assert_eq!(answers.iter().count(), 3);
+}
+
+#[test]
+fn rejects_invalid_answers() {
+    for data in [
+        "[twelve.part1]\nex1 = 1\n",
+        "[day12.part3]\nex1 = 1\n",
+        "[day12.part1]\nex1 = 1.5\n",
+        "day12 = 1\n",
+    ] {
+        assert!(
+            matches!(Answers::parse(data), Err(AnswersError::Invalid(_))),
+            "{:?}",
+            data
+        );
+    }
+    assert!(matches!(
+        Answers::parse("[day12"),
+        Err(AnswersError::Toml(_))
+    ));
+}
+
+#[test]
+#[ignore = "needs personal inputs and answers"]
+fn personal_inputs() {
+    let data_dir = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/data"));
+    let inputs =
+        Inputs::new(std::env::var_os("AOC_INPUTS").map_or(data_dir.clone(), PathBuf::from));
+    let answers_path =
+        std::env::var_os("AOC_ANSWERS").map_or(data_dir.join("answers.toml"), PathBuf::from);
+    let answers = Answers::load(&answers_path).unwrap();
+    assert!(
+        !answers.is_empty(),
+        "no answers in {}",
+        answers_path.display()
+    );
+
+    let mut failures = Vec::new();
+    for (day, part, file, expected) in answers.iter() {
+        let label = format!("day {} part {} on {}", day, part, file);
+        let day = registry::find(day).unwrap_or_else(|| panic!("{}: unknown day", label));
+
+        let result = inputs
+            .load(day.number, file)
+            .map_err(|err| err.to_string())
+            .and_then(|data| (day.parse)(&data).map_err(|err| err.to_string()))
+            .and_then(|parsed| parsed.solve(part).map_err(|err| err.to_string()));
+
+        match result {
+            Ok(answer) if answer == *expected => {}
+            Ok(answer) => {
+                failures.push(format!("{}: expected {}, got {}", label, expected, answer))
+            }
+            Err(err) => failures.push(format!("{}: {}", label, err)),
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,58 @@
+use aoc24::bench::{
+    Report,
+    Timing,
+};
+
+fn report(timings: &[(u8, u64, u64, u64)]) -> Report {
+    Report {
+        timings: timings
+            .iter()
+            .map(|&(day, parse_ns, part1_ns, part2_ns)| {
+                Timing {
+                    day,
+                    parse_ns,
+                    part1_ns,
+                    part2_ns,
+                }
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn tabulates_changes() {
+    let baseline = report(&[(1, 1_000, 2_000_000, 500)]);
+    let current = report(&[(1, 1_500, 1_000_000, 500), (2, 20, 3_000_000_000, 0)]);
+
+    let table = current.table(Some(&baseline));
+    let rows: Vec<_> = table.lines().skip(2).collect();
+    assert_eq!(
+        rows[0],
+        "   1 |       1.5µs (+50%) |      1.00ms (-50%) |        500ns (+0%)"
+    );
+    // Days missing from the baseline have no change
+    assert_eq!(
+        rows[1],
+        "   2 |               20ns |              3.00s |                0ns"
+    );
+    assert!(!current.table(None).contains('%'));
+}
+
+#[test]
+fn flags_regressions_over_threshold() {
+    let baseline = report(&[(1, 1_000, 1_000, 1_000), (2, 1_000, 1_000, 1_000)]);
+    let current = report(&[
+        (1, 1_050, 1_200, 900),
+        (2, 5_000, 1_000, 1_000),
+        (3, 9, 9, 9),
+    ]);
+
+    assert_eq!(
+        current.regressions(&baseline, 10.0),
+        [
+            "day 1 part 1: 1.0µs -> 1.2µs (+20%)",
+            "day 2 parse: 1.0µs -> 5.0µs (+400%)",
+        ]
+    );
+    assert_eq!(current.regressions(&baseline, 500.0), Vec::<String>::new());
+}
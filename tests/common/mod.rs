@@ -0,0 +1,16 @@
+//! Helpers shared by the integration tests.
+
+use aoc24::{
+    input::Inputs,
+    solution::Solution,
+};
+
+/// Committed data `file` of `day`, parsed by `S`.
+pub fn load<S: Solution>(
+    day: u8,
+    file: &str,
+) -> S::Input {
+    let inputs = Inputs::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data"));
+    let data = inputs.load(day, file).unwrap();
+    S::parse(&data).unwrap()
+}
@@ -0,0 +1,81 @@
+use aoc24::{
+    day10::{
+        Day10,
+        Topology,
+        TrailRules,
+    },
+    error::SolveError,
+    solution::Solution,
+};
+
+mod common;
+
+fn parse(data: &str) -> Topology {
+    Day10::parse(data).unwrap()
+}
+
+#[test]
+fn enumerated_paths_match_ratings() {
+    let topology = common::load::<Day10>(10, "ex1");
+    let rules = TrailRules::default();
+    let trails = topology.trails(&rules).unwrap();
+
+    for trailhead in topology.trailheads(&rules) {
+        let paths: Vec<_> = topology.paths(trailhead, &rules).collect();
+
+        assert_eq!(paths.len() as u64, trails.rating(trailhead));
+        assert!(paths.iter().all(|path| path.len() == 10));
+    }
+}
+
+#[test]
+fn skips_impassable_cells() {
+    let rules = TrailRules::default();
+
+    let topology = parse("..90..9\n...1.98\n...2..7\n6543456\n765.987\n876....\n987....\n");
+    assert_eq!(topology.totals(&rules).unwrap().0, 4);
+
+    let topology = parse(".....0.\n..4321.\n..5..2.\n..6543.\n..7..4.\n..8765.\n..9....\n");
+    assert_eq!(topology.totals(&rules).unwrap().1, 3);
+}
+
+#[test]
+fn follows_custom_rules() {
+    let topology = common::load::<Day10>(10, "ex1");
+    let descending = TrailRules::new(9, 0, -1..=-1, false).unwrap();
+    assert_eq!(topology.totals(&descending).unwrap(), (36, 81));
+
+    let topology = parse("0.\n.1\n");
+    assert_eq!(
+        topology
+            .totals(&TrailRules::new(0, 1, 1..=1, false).unwrap())
+            .unwrap(),
+        (0, 0)
+    );
+    assert_eq!(
+        topology
+            .totals(&TrailRules::new(0, 1, 1..=1, true).unwrap())
+            .unwrap(),
+        (1, 1)
+    );
+
+    assert!(TrailRules::new(0, 9, -1..=1, false).is_err());
+}
+
+#[test]
+fn reports_rating_overflow() {
+    // Every cell steps diagonally to three cells of the next row, so each of the 600 trailheads
+    // has about 3^35 trails
+    let data: String = (0..36)
+        .map(|height| {
+            let c = char::from_digit(height, 36).unwrap();
+            format!("{}\n", c.to_string().repeat(600))
+        })
+        .collect();
+    let rules = TrailRules::new(0, 35, 1..=1, true).unwrap();
+
+    assert!(matches!(
+        parse(&data).totals(&rules),
+        Err(SolveError::Overflow(_))
+    ));
+}
@@ -0,0 +1,87 @@
+use aoc24::{
+    day11::{
+        count,
+        statistics,
+        Action,
+        Condition,
+        Day11,
+        Rule,
+        Rules,
+        SimpleRule,
+    },
+    error::SolveError,
+    solution::Solution,
+};
+use num_bigint::BigUint;
+
+#[test]
+fn splits_digits_arithmetically() {
+    let split = SimpleRule {
+        condition: Condition::EvenDigits,
+        action: Action::Split,
+    };
+
+    assert_eq!(split.apply(1000), Some(Ok(vec![10, 0])));
+    assert_eq!(split.apply(253000), Some(Ok(vec![253, 0])));
+    assert_eq!(split.apply(17), Some(Ok(vec![1, 7])));
+    assert_eq!(split.apply(0), None);
+    assert_eq!(split.apply(125), None);
+}
+
+#[test]
+fn parses_rules() {
+    let stones = Day11::parse("125 17\n").unwrap();
+
+    let standard = Rules::parse("0 -> 1\neven -> split\n* -> *2024\n").unwrap();
+    assert_eq!(count::<u64>(&stones, 25, &standard), Ok(Some(55312)));
+
+    let increment = Rules::parse("* -> +1; * -> 0").unwrap();
+    assert_eq!(count::<u64>(&stones, 3, &increment), Ok(Some(2)));
+
+    assert!(Rules::parse("0 => 1").is_err());
+}
+
+#[test]
+fn counts_past_fixed_size_integers() {
+    let stones = Day11::parse("125 17\n").unwrap();
+    let rules = Rules::standard();
+
+    assert!(matches!(
+        count::<u64>(&stones, 200, &rules),
+        Err(SolveError::Overflow(_))
+    ));
+    assert_eq!(
+        count::<u128>(&stones, 75, &rules)
+            .unwrap()
+            .map(BigUint::from),
+        count::<BigUint>(&stones, 75, &rules).unwrap()
+    );
+    assert!(count::<BigUint>(&stones, 1000, &rules).is_ok());
+}
+
+#[test]
+fn records_each_blink() {
+    let stones = Day11::parse("125 17\n").unwrap();
+    let statistics = statistics::<u64>(&stones, 6, &Rules::standard()).unwrap();
+
+    // 125 17 -> 253000 1 7 -> 253 0 2024 14168 -> ... -> 22 stones after 6 blinks.
+    let totals: Vec<u64> = statistics.blinks.iter().map(|stats| stats.total).collect();
+    assert_eq!(totals, [2, 3, 4, 5, 9, 13, 22]);
+    assert_eq!(statistics.blinks[1].largest, 253000);
+    assert_eq!(statistics.closed_at, None);
+
+    let csv = statistics.csv();
+    assert_eq!(csv.lines().count(), 8);
+    assert_eq!(csv.lines().nth(1), Some("0,2,2,2,125"));
+}
+
+#[test]
+fn detects_when_values_stop_appearing() {
+    let stones = Day11::parse("0\n").unwrap();
+    let statistics = statistics::<u64>(&stones, 100, &Rules::standard()).unwrap();
+    let closed_at = statistics.closed_at.unwrap();
+
+    assert!(statistics.blinks[closed_at..]
+        .iter()
+        .all(|stats| stats.seen == statistics.blinks[closed_at].seen));
+}
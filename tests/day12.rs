@@ -0,0 +1,84 @@
+use aoc24::{
+    day12::Day12,
+    grid::Point,
+    solution::Solution,
+};
+
+mod common;
+
+#[test]
+fn labels_zones() {
+    let regions = common::load::<Day12>(12, "ex1").regions();
+
+    // The two `C` zones are not connected, nor are the two `I` ones.
+    assert_eq!(regions.zones().len(), 11);
+    let labels = regions.labels();
+    assert_eq!(labels[Point::new(0, 6)], labels[Point::new(3, 3)]);
+    assert_ne!(labels[Point::new(0, 6)], labels[Point::new(4, 7)]);
+    assert_eq!(regions.zones()[labels[Point::new(4, 7)]].area(), 1);
+}
+
+#[test]
+fn measures_zones() {
+    let regions = common::load::<Day12>(12, "ex2").regions();
+    let measures: Vec<_> = regions
+        .zones()
+        .iter()
+        .map(|zone| (zone.garden(), zone.area(), zone.perimeter(), zone.sides()))
+        .collect();
+
+    assert_eq!(
+        measures,
+        [('A', 28, 40, 12), ('B', 4, 8, 4), ('B', 4, 8, 4)]
+    );
+}
+
+#[test]
+fn draws_corners_and_measures() {
+    let regions = common::load::<Day12>(12, "ex2").regions();
+
+    let corners = regions.corners();
+    assert_eq!(corners.iter().map(|(_, count)| count).sum::<usize>(), 20);
+    // Both `B` zones and the `A` zone around them meet at the center.
+    assert_eq!(corners[Point::new(3, 3)], 4);
+
+    let svg = regions.svg();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(">A 28/40/12</text>"));
+    assert_eq!(svg.matches("<circle").count(), 11);
+}
+
+#[test]
+fn finds_holes() {
+    let regions = Day12::parse("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n")
+        .unwrap()
+        .regions();
+    let enclosures = regions.enclosures();
+
+    assert_eq!(enclosures[0].holes, 4);
+    assert_eq!(enclosures[0].inner_perimeter, 16);
+    assert_eq!(regions.zones()[0].perimeter(), 36);
+    assert_eq!(enclosures[0].enclosed, [1, 2, 3, 4]);
+    assert!(enclosures[1..]
+        .iter()
+        .all(|enclosure| enclosure.parent == Some(0) && enclosure.holes == 0));
+}
+
+#[test]
+fn builds_containment_tree() {
+    let regions = Day12::parse("AAAAA\nABBBA\nABCBA\nABBBA\nAAAAA\n")
+        .unwrap()
+        .regions();
+    let enclosures = regions.enclosures();
+    let parents: Vec<_> = enclosures
+        .iter()
+        .map(|enclosure| enclosure.parent)
+        .collect();
+
+    assert_eq!(parents, [None, Some(0), Some(1)]);
+    assert_eq!(enclosures[0].enclosed, [1, 2]);
+    assert_eq!(
+        regions.zones()[1].bounds(),
+        (Point::new(1, 1), Point::new(3, 3))
+    );
+}
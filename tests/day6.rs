@@ -0,0 +1,88 @@
+use aoc24::{
+    ansi,
+    day6::{
+        loops_csv,
+        Day6,
+        Outcome,
+    },
+    grid::{
+        Direction,
+        Point,
+    },
+};
+
+mod common;
+
+#[test]
+fn lists_loop_obstructions() {
+    let puzzle = common::load::<Day6>(6, "ex1");
+    let guard = puzzle.guard().unwrap();
+
+    // The six positions given in the puzzle statement.
+    assert_eq!(
+        puzzle.loop_obstructions(guard),
+        [(6, 3), (7, 6), (7, 7), (8, 1), (8, 3), (9, 7)].map(|(row, col)| Point::new(row, col))
+    );
+}
+
+#[test]
+fn describes_loops() {
+    let puzzle = common::load::<Day6>(6, "ex1");
+    let guard = puzzle.guard().unwrap();
+    let loops = puzzle.loops(guard);
+
+    let first = loops[0];
+    assert_eq!(first.obstruction, Point::new(6, 3));
+    assert_eq!(first.length, 18);
+    assert_eq!(first.entry.position, Point::new(6, 4));
+    assert_eq!(first.entry.direction, Direction::Up);
+
+    let csv = loops_csv(&loops);
+    assert_eq!(csv.lines().count(), 7);
+    assert_eq!(csv.lines().nth(1), Some("6,3,18,6,4,Up"));
+}
+
+#[test]
+fn checks_obstructions() {
+    let puzzle = common::load::<Day6>(6, "ex1");
+    let guard = puzzle.guard().unwrap();
+
+    assert!(puzzle.check_obstruction(guard, Point::new(6, 3)).is_ok());
+    for (row, col) in [(10, 7), (0, -1), (0, 4), (6, 4)] {
+        assert!(puzzle
+            .check_obstruction(guard, Point::new(row, col))
+            .is_err());
+    }
+}
+
+#[test]
+fn animates_patrol() {
+    let puzzle = common::load::<Day6>(6, "ex1");
+    let guard = puzzle.guard().unwrap();
+
+    let (outcome, frames) = puzzle.animation(guard, None);
+    let frames: Vec<_> = frames.collect();
+    assert_eq!(outcome, Outcome::OutOfBounds);
+    assert!(frames[0].starts_with("step 0: guard at (6, 4) facing Up\n"));
+    assert!(frames
+        .last()
+        .unwrap()
+        .starts_with("step 54: guard at (9, 7) facing Down\n"));
+    // A title line and one line per row
+    assert!(frames.iter().all(|frame| frame.lines().count() == 11));
+
+    let (outcome, frames) = puzzle.animation(guard, Some(Point::new(6, 3)));
+    assert_eq!(outcome, Outcome::Loop);
+    let mut out = Vec::new();
+    ansi::animate(&mut out, frames, 0.0).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("\x1b[?25l"));
+    assert!(out.ends_with("\x1b[?25h"));
+    assert!(out.contains("step 0: guard at (6, 4) facing Up"));
+
+    // Too slow for the delay between frames to be represented
+    let (_, frames) = puzzle.animation(guard, None);
+    let mut out = Vec::new();
+    assert!(ansi::animate(&mut out, frames, 1e-300).is_err());
+    assert!(out.is_empty());
+}
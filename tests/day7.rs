@@ -0,0 +1,99 @@
+use aoc24::{
+    day7::{
+        Day7,
+        Operators,
+    },
+    solution::{
+        Part,
+        Solution,
+    },
+};
+
+mod common;
+
+fn equations(
+    data: &str,
+    operators: &str,
+) -> Vec<Option<String>> {
+    let operators = Operators::parse(operators).unwrap();
+    Day7::parse(data)
+        .unwrap()
+        .iter()
+        .map(|calibration| {
+            calibration
+                .solve(&operators)
+                .map(|equation| equation.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn traces_equations() {
+    let data = "3267: 81 40 27\n7290: 6 8 6 15\n83: 17 5\n";
+
+    assert_eq!(
+        equations(data, "+ * ||"),
+        [
+            Some("3267 = 81 * 40 + 27".to_string()),
+            Some("7290 = 6 * 8 || 6 * 15".to_string()),
+            None,
+        ]
+    );
+    assert_eq!(equations(data, "+ *")[1], None);
+}
+
+#[test]
+fn undoes_edge_numbers() {
+    assert_eq!(
+        equations("50: 5 0\n0: 7 3 0\n7: 0 7\n", "+ * ||"),
+        [
+            Some("50 = 5 || 0".to_string()),
+            Some("0 = 7 + 3 * 0".to_string()),
+            Some("7 = 0 + 7".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn registers_operators() {
+    let data = "5: 12 7\n6: 3 5\n2: 9 4\n3: 9 4 1\n13: 1 5\n";
+
+    assert_eq!(
+        equations(data, "+ * - ^ / ||2"),
+        [
+            Some("5 = 12 - 7".to_string()),
+            Some("6 = 3 ^ 5".to_string()),
+            Some("2 = 9 / 4".to_string()),
+            Some("3 = 9 / 4 + 1".to_string()),
+            Some("13 = 1 ||2 5".to_string()),
+        ]
+    );
+    assert_eq!(equations(data, "+ *"), [None, None, None, None, None]);
+    assert!(Operators::parse("+ %").is_err());
+    assert!(Operators::parse("||1").is_err());
+    assert!(Operators::parse(" , ").is_err());
+}
+
+#[test]
+fn reports_progression() {
+    let operators = Operators::parse("+ * ||").unwrap();
+    let steps = operators.progression(&common::load::<Day7>(7, "ex1"));
+    let solvable: Vec<_> = steps
+        .iter()
+        .map(|step| (step.operator.to_string(), step.equations.len(), step.total))
+        .collect();
+
+    assert_eq!(
+        solvable,
+        [
+            ("+".to_string(), 0, 0),
+            ("*".to_string(), 3, 3749),
+            ("||".to_string(), 3, 11387),
+        ]
+    );
+    assert_eq!(steps[1].equations[0].to_string(), "190 = 10 * 19");
+    assert_eq!(
+        Operators::standard(Part::Second).progression(&common::load::<Day7>(7, "ex1"))[2].total,
+        11387
+    );
+}
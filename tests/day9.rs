@@ -0,0 +1,138 @@
+use aoc24::{
+    day9::{
+        checksum,
+        Day9,
+        Move,
+        Strategy,
+    },
+    solution::Solution,
+};
+
+mod common;
+
+#[test]
+fn shows_file_moves() {
+    let disk = common::load::<Day9>(9, "ex1");
+    let compaction = disk.compact_files(Strategy::FirstFit);
+    let layouts: Vec<_> = disk.layouts(&compaction.moves).collect();
+
+    // Files 8, 6, 5, 3, 1 and 0 cannot move left.
+    assert_eq!(
+        layouts,
+        [
+            "00...111...2...333.44.5555.6666.777.888899",
+            "0099.111...2...333.44.5555.6666.777.8888..",
+            "0099.1117772...333.44.5555.6666.....8888..",
+            "0099.111777244.333....5555.6666.....8888..",
+            "00992111777.44.333....5555.6666.....8888..",
+        ]
+    );
+    assert_eq!(
+        compaction.moves_csv(),
+        "id,from,to,size\n9,40,2,2\n7,32,8,3\n4,19,12,2\n2,11,4,1\n"
+    );
+}
+
+#[test]
+fn logs_block_moves() {
+    let disk = common::load::<Day9>(9, "ex1");
+    let compaction = disk.compact_blocks();
+
+    // File 8 is split: its last block fills what 9 left of the first free span, the other three
+    // the second span.
+    assert_eq!(
+        compaction.moves[..4],
+        [
+            Move {
+                id: 9,
+                from: 40,
+                to: 2,
+                size: 2,
+            },
+            Move {
+                id: 8,
+                from: 39,
+                to: 4,
+                size: 1,
+            },
+            Move {
+                id: 8,
+                from: 36,
+                to: 8,
+                size: 3,
+            },
+            Move {
+                id: 7,
+                from: 32,
+                to: 12,
+                size: 3,
+            },
+        ]
+    );
+    assert_eq!(
+        disk.layouts(&compaction.moves).last().unwrap(),
+        "0099811188827773336446555566.............."
+    );
+}
+
+#[test]
+fn compares_strategies() {
+    // Starts as `0...1..233`: first-fit and worst-fit move file 3 to the span of three blocks,
+    // best-fit to the span of two, leaving different spans for files 2 and 1.
+    let disk = Day9::parse("1312102").unwrap();
+    let report = |strategy| {
+        let compaction = disk.compact_files(strategy);
+        (
+            disk.layouts(&compaction.moves).last().unwrap(),
+            checksum(&compaction.extents),
+            compaction.fragmentation(),
+            compaction.moved(),
+        )
+    };
+
+    assert_eq!(
+        report(Strategy::FirstFit),
+        ("03321.....".to_string(), 19, (0, 0), 3)
+    );
+    assert_eq!(
+        report(Strategy::BestFit),
+        ("021..33...".to_string(), 37, (1, 2), 4)
+    );
+    assert_eq!(
+        report(Strategy::WorstFit),
+        ("0331.2....".to_string(), 22, (1, 1), 4)
+    );
+    assert_eq!(
+        report(Strategy::Defragment),
+        ("01233.....".to_string(), 26, (0, 0), 4)
+    );
+}
+
+#[test]
+fn leaves_empty_files() {
+    // File 1 is empty: it has no block to move, so only file 2 moves
+    let disk = Day9::parse("12001").unwrap();
+    for strategy in Strategy::ALL {
+        let compaction = disk.compact_files(strategy);
+        assert_eq!(
+            compaction.moves,
+            [Move {
+                id: 2,
+                from: 3,
+                to: 1,
+                size: 1,
+            }]
+        );
+    }
+}
+
+#[test]
+fn merges_free_spans_around_empty_files() {
+    // Empty file 5 sits between spans of 4 and 5 blocks, together large enough for file 6's 7
+    let disk = Day9::parse("597651299405758181081105").unwrap();
+    assert_eq!(
+        checksum(&disk.compact_files(Strategy::FirstFit).extents),
+        7432
+    );
+    assert_eq!(Day9::part2(&disk).unwrap().to_string(), "7432");
+}
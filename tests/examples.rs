@@ -0,0 +1,102 @@
+//! Answers given in the puzzle statements for the committed example files.
+
+use aoc24::{
+    answer::Answer,
+    input::Inputs,
+    registry,
+    solution::Part,
+};
+
+fn check(
+    day: u8,
+    part: Part,
+    file: &str,
+    expected: impl Into<Answer>,
+) {
+    let inputs = Inputs::new(concat!(env!("CARGO_MANIFEST_DIR"), "/data"));
+    let data = inputs.load(day, file).unwrap();
+    let parsed = (registry::find(day).unwrap().parse)(&data).unwrap();
+
+    assert_eq!(
+        parsed.solve(part).unwrap(),
+        expected.into(),
+        "day {} part {} on {}",
+        day,
+        part,
+        file
+    );
+}
+
+#[test]
+fn day1() {
+    check(1, Part::First, "ex1", 11);
+    check(1, Part::Second, "ex1", 31);
+}
+
+#[test]
+fn day2() {
+    check(2, Part::First, "ex1", 2);
+    check(2, Part::Second, "ex1", 4);
+}
+
+#[test]
+fn day3() {
+    check(3, Part::First, "ex1", 161);
+    check(3, Part::Second, "ex2", 48);
+}
+
+#[test]
+fn day4() {
+    check(4, Part::First, "ex1", 18);
+    check(4, Part::Second, "ex1", 9);
+}
+
+#[test]
+fn day5() {
+    check(5, Part::First, "ex1", 143);
+    check(5, Part::Second, "ex1", 123);
+}
+
+#[test]
+fn day6() {
+    check(6, Part::First, "ex1", 41);
+    check(6, Part::Second, "ex1", 6);
+}
+
+#[test]
+fn day7() {
+    check(7, Part::First, "ex1", 3749);
+    check(7, Part::Second, "ex1", 11387);
+}
+
+#[test]
+fn day8() {
+    check(8, Part::First, "ex1", 14);
+    check(8, Part::Second, "ex1", 34);
+}
+
+#[test]
+fn day9() {
+    check(9, Part::First, "ex1", 1928);
+    check(9, Part::Second, "ex1", 2858);
+}
+
+#[test]
+fn day10() {
+    check(10, Part::First, "ex1", 36);
+    check(10, Part::Second, "ex1", 81);
+}
+
+#[test]
+fn day11() {
+    check(11, Part::First, "ex1", 55312);
+    check(11, Part::Second, "ex1", 65601038650482_i64);
+}
+
+#[test]
+fn day12() {
+    check(12, Part::First, "ex1", 1930);
+    check(12, Part::First, "ex2", 1184);
+    check(12, Part::Second, "ex1", 1206);
+    check(12, Part::Second, "ex2", 368);
+}
@@ -0,0 +1,59 @@
+use aoc24::grid::{
+    parse_chars,
+    Direction,
+    Grid,
+    Point,
+};
+
+fn sample() -> Grid<char> {
+    parse_chars("abc\r\ndef\n", Some, "a letter").unwrap()
+}
+
+#[test]
+fn parses_rows_of_chars() {
+    let grid = sample();
+
+    assert_eq!(grid.num_rows(), 2);
+    assert_eq!(grid.num_cols(), 3);
+    assert_eq!(grid[Point::new(1, 2)], 'f');
+    assert_eq!(grid.get(Point::new(2, 0)), None);
+    assert_eq!(grid.to_string(), "abc\ndef\n");
+}
+
+#[test]
+fn reports_ragged_rows() {
+    let err = parse_chars("abc\nde\n", Some, "a letter").unwrap_err();
+
+    assert_eq!((err.line, err.column), (2, 1));
+    assert_eq!(err.expected, "a row of 3 cells");
+}
+
+#[test]
+fn neighbours_stay_within_bounds() {
+    let grid = sample();
+
+    let corner = grid.neighbours(Point::new(0, 0)).collect::<Vec<_>>();
+    assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+
+    assert_eq!(grid.neighbours8(Point::new(0, 1)).count(), 5);
+}
+
+#[test]
+fn rays_stop_at_the_edge() {
+    let grid = sample();
+
+    let ray = grid
+        .ray(Point::new(0, 0), Direction::Right.delta())
+        .map(|point| grid[point])
+        .collect::<String>();
+    assert_eq!(ray, "abc");
+
+    assert_eq!(grid.ray(Point::new(0, 0), Point::new(1, 3)).count(), 1);
+}
+
+#[test]
+fn parses_points() {
+    assert_eq!("3,-4".parse(), Ok(Point::new(3, -4)));
+    assert_eq!(" 1, 2 ".parse(), Ok(Point::new(1, 2)));
+    assert!("1;2".parse::<Point>().is_err());
+}
@@ -0,0 +1,53 @@
+use aoc24::{
+    day10::Day10,
+    day7::Day7,
+    error::{
+        finish,
+        ParseError,
+    },
+    solution::Solution,
+};
+use nom::bytes::complete::is_not;
+
+fn position(error: ParseError) -> (usize, usize, Option<char>) {
+    (error.line, error.column, error.found)
+}
+
+#[test]
+fn locates_errors_on_later_lines() {
+    let error = Day10::parse("0123\n45#7\n").unwrap_err();
+    assert_eq!(position(error), (2, 3, Some('#')));
+
+    // Columns count characters, not bytes
+    let data = "é.\néé!";
+    let error = ParseError::at(data, data.find('!').unwrap(), "a letter");
+    assert_eq!(position(error), (2, 3, Some('!')));
+}
+
+#[test]
+fn locates_errors_in_crlf_input() {
+    let error = Day10::parse("0123\r\n4567\r\n89#1\r\n").unwrap_err();
+    assert_eq!(position(error), (3, 3, Some('#')));
+
+    assert!(Day7::parse("190: 10 19\r\n83: 17 5\r\n").is_ok());
+    let error = Day7::parse("190: 10 19\r\n83 17 5\r\n").unwrap_err();
+    assert_eq!(position(error), (2, 1, Some('8')));
+}
+
+#[test]
+fn locates_trailing_blank_lines() {
+    let error = Day7::parse("190: 10 19\n\n").unwrap_err();
+    assert_eq!(position(error), (2, 1, Some('\n')));
+
+    let error = Day10::parse("0123\n\n").unwrap_err();
+    assert_eq!(error.expected, "a row of 4 cells");
+    assert_eq!(position(error), (2, 1, Some('\n')));
+}
+
+#[test]
+fn describes_missing_text() {
+    let data = "\nabc";
+    let error = finish(data, is_not::<_, _, nom::error::Error<_>>("\r\n")(data)).unwrap_err();
+    assert_eq!(error.expected, "some text");
+    assert_eq!(position(error), (1, 1, Some('\n')));
+}
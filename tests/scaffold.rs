@@ -0,0 +1,112 @@
+use aoc24::scaffold::{
+    scaffold,
+    ScaffoldError,
+};
+use std::{
+    fs,
+    path::PathBuf,
+};
+
+const LIB: &str = "pub mod answer;
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day9;
+pub mod error;
+";
+
+const REGISTRY: &str = "use crate::{
+    day1,
+    day10,
+    day2,
+    day9,
+    solution::{
+        parser,
+        Parser,
+    },
+};
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        parse: parser::<day1::Day1>,
+        examples: [&[\"ex1\"], &[\"ex1\"]],
+    },
+];
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|entry| entry.number == day)
+}
+";
+
+/// Project with copies of the files the scaffold edits, in a fresh temporary directory.
+fn project(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc24-scaffold-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::create_dir_all(root.join("tests")).unwrap();
+    fs::write(root.join("src/lib.rs"), LIB).unwrap();
+    fs::write(root.join("src/registry.rs"), REGISTRY).unwrap();
+    fs::write(root.join("tests/examples.rs"), "").unwrap();
+    root
+}
+
+#[test]
+fn registers_new_day() {
+    let root = project("new");
+    scaffold(&root, 11).unwrap();
+
+    let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+    assert_eq!(
+        lib,
+        "pub mod answer;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day9;
+pub mod error;
+"
+    );
+
+    let registry = fs::read_to_string(root.join("src/registry.rs")).unwrap();
+    assert!(registry.contains("    day10,\n    day11,\n    day2,\n"));
+    assert!(registry.contains(
+        "        examples: [&[\"ex1\"], &[\"ex1\"]],
+    },
+    Day {
+        number: 11,
+        parse: parser::<day11::Day11>,
+        examples: [&[\"ex1\"], &[\"ex1\"]],
+    },
+];
+"
+    ));
+
+    assert!(root.join("src/day11.rs").exists());
+    assert!(root.join("data/day11/ex1").exists());
+    assert!(matches!(
+        scaffold(&root, 11),
+        Err(ScaffoldError::AlreadyExists(_))
+    ));
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn rejects_unexpected_registry() {
+    let root = project("unexpected");
+    fs::write(
+        root.join("src/registry.rs"),
+        "pub const DAYS: &[Day] = &[];\n",
+    )
+    .unwrap();
+
+    assert!(matches!(
+        scaffold(&root, 11),
+        Err(ScaffoldError::Unexpected { .. })
+    ));
+    // Nothing is written when a file cannot be updated
+    assert!(!root.join("src/day11.rs").exists());
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), LIB);
+    fs::remove_dir_all(&root).unwrap();
+}